@@ -1,4 +1,11 @@
+pub mod background;
+pub mod bvh;
 pub mod camera;
+pub mod integrator;
 pub mod ray;
+pub mod renderer;
+pub mod sampler;
 pub mod sphere;
 pub mod traceable;
+pub mod triangle;
+pub mod triangle_mesh;
@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use crate::types::{
+    color::{Color, COLOR_BLACK},
+    framebuffer::Framebuffer,
+    vec3::Vec3,
+};
+use crate::utils::config::{BackgroundConfig, Config};
+use crate::utils::hdr_loader::{load_hdr, HdrError};
+use crate::utils::utilities::{degrees_to_radians, PI};
+
+/// Radiance seen by rays that miss every object in the scene
+#[derive(Debug, Clone)]
+pub enum Background {
+    Solid(Color),
+    Gradient {
+        bottom: Color,
+        top: Color,
+    },
+    /// Equirectangular image, rotated about the vertical axis by `rotation` radians
+    EnvironmentMap {
+        image: Framebuffer,
+        rotation: f64,
+        intensity: f64,
+    },
+}
+
+impl Background {
+    /// Create the configured background, loading the environment map if there is one
+    pub fn configure(configuration: &Config) -> Result<Self, HdrError> {
+        Background::configure_relative_to(configuration, Path::new(""))
+    }
+
+    /// Create the configured background, resolving a relative environment map path
+    /// against the given directory
+    pub fn configure_relative_to(
+        configuration: &Config,
+        directory: &Path,
+    ) -> Result<Self, HdrError> {
+        Ok(match configuration.background_config() {
+            BackgroundConfig::Black {} => Background::Solid(COLOR_BLACK),
+            BackgroundConfig::Solid { color } => Background::Solid(*color),
+            BackgroundConfig::Gradient { bottom, top } => Background::Gradient {
+                bottom: *bottom,
+                top: *top,
+            },
+            BackgroundConfig::EnvironmentMap {
+                file,
+                rotation,
+                intensity,
+            } => Background::EnvironmentMap {
+                image: load_hdr(&directory.join(file).to_string_lossy())?,
+                rotation: degrees_to_radians(rotation.unwrap_or(0.0)),
+                intensity: intensity.unwrap_or(1.0),
+            },
+        })
+    }
+
+    /// Color of the background in the given ray direction
+    pub fn color(&self, direction: &Vec3) -> Color {
+        match self {
+            Background::Solid(color) => *color,
+            Background::Gradient { bottom, top } => {
+                let t = (direction
+                    .unit_vector()
+                    .expect("Weirdly, the ray is heading in the null direction")
+                    .y()
+                    + 1.0)
+                    * 0.5;
+
+                bottom.scaled(1.0 - t) + top.scaled(t)
+            }
+            Background::EnvironmentMap {
+                image,
+                rotation,
+                intensity,
+            } => {
+                let unit_direction = direction
+                    .unit_vector()
+                    .expect("Weirdly, the ray is heading in the null direction");
+
+                // -Z maps to the center of the image and +Y to its top row
+                let phi = unit_direction.x().atan2(-unit_direction.z()) - rotation;
+                let theta = unit_direction.y().clamp(-1.0, 1.0).acos();
+                let u = (0.5 + phi / (2.0 * PI)).rem_euclid(1.0);
+                let v = theta / PI;
+
+                Background::sample_bilinear(image, u, v).scaled(*intensity)
+            }
+        }
+    }
+
+    /// Bilinearly filtered image color, wrapping around horizontally
+    fn sample_bilinear(image: &Framebuffer, u: f64, v: f64) -> Color {
+        let width = image.width();
+        let height = image.height();
+
+        let x = u * width as f64 - 0.5;
+        let y = (v * height as f64 - 0.5).clamp(0.0, (height - 1) as f64);
+        let x_0 = x.floor();
+        let y_0 = y.floor();
+        let x_weight = x - x_0;
+        let y_weight = y - y_0;
+
+        let column_0 = (x_0 as i64).rem_euclid(width as i64) as usize;
+        let column_1 = (column_0 + 1) % width;
+        let row_0 = y_0 as usize;
+        let row_1 = (row_0 + 1).min(height - 1);
+
+        let top = image.pixel(column_0, row_0).scaled(1.0 - x_weight)
+            + image.pixel(column_1, row_0).scaled(x_weight);
+        let bottom = image.pixel(column_0, row_1).scaled(1.0 - x_weight)
+            + image.pixel(column_1, row_1).scaled(x_weight);
+
+        top.scaled(1.0 - y_weight) + bottom.scaled(y_weight)
+    }
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use crate::types::{aabb::Aabb, hit_record::HitRecord};
+use crate::utils::render_stats;
+
+use super::{
+    ray::Ray,
+    traceable::{Traceable, TraceableGroup},
+};
+
+/// Node of a bounding volume hierarchy. Rays only test the children of a node
+/// if they pass through the box surrounding both of them
+pub struct BvhNode {
+    d_left: Box<dyn Traceable>,
+    d_right: Option<Box<dyn Traceable>>,
+    d_box: Aabb,
+}
+
+impl BvhNode {
+    /// Build a hierarchy from the objects of a group. All objects must be bounded
+    pub fn from_group(group: TraceableGroup) -> Result<Self, &'static str> {
+        if group.objects.is_empty() {
+            return Err("Cannot build a BVH from an empty group");
+        }
+
+        let mut bounded_objects = Vec::with_capacity(group.objects.len());
+        for object in group.objects {
+            let bounding_box = object
+                .bounding_box()
+                .ok_or("Cannot build a BVH containing unbounded objects")?;
+            bounded_objects.push((bounding_box, object));
+        }
+
+        Ok(BvhNode::build(bounded_objects))
+    }
+
+    fn build(mut objects: Vec<(Aabb, Box<dyn Traceable>)>) -> Self {
+        // Split along the axis where object centers are most spread out
+        let centroid_bounds = objects
+            .iter()
+            .map(|(bounding_box, _)| Aabb::new(&bounding_box.centroid(), &bounding_box.centroid()))
+            .reduce(|box_0, box_1| Aabb::surrounding_box(&box_0, &box_1))
+            .unwrap();
+        let centroid_extent = *centroid_bounds.maximum() - *centroid_bounds.minimum();
+        let axis = if centroid_extent.x() >= centroid_extent.y()
+            && centroid_extent.x() >= centroid_extent.z()
+        {
+            0
+        } else if centroid_extent.y() >= centroid_extent.z() {
+            1
+        } else {
+            2
+        };
+
+        objects.sort_by(|(box_a, _), (box_b, _)| {
+            let a = box_a.axis_minimum(axis) + box_a.axis_maximum(axis);
+            let b = box_b.axis_minimum(axis) + box_b.axis_maximum(axis);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        match objects.len() {
+            1 => {
+                let (left_box, left) = objects.pop().unwrap();
+                BvhNode {
+                    d_left: left,
+                    d_right: None,
+                    d_box: left_box,
+                }
+            }
+            2 => {
+                let (right_box, right) = objects.pop().unwrap();
+                let (left_box, left) = objects.pop().unwrap();
+                BvhNode {
+                    d_left: left,
+                    d_right: Some(right),
+                    d_box: Aabb::surrounding_box(&left_box, &right_box),
+                }
+            }
+            _ => {
+                let right_objects = objects.split_off(objects.len() / 2);
+                let left = BvhNode::build(objects);
+                let right = BvhNode::build(right_objects);
+                let node_box = Aabb::surrounding_box(&left.d_box, &right.d_box);
+                BvhNode {
+                    d_left: Box::new(left),
+                    d_right: Some(Box::new(right)),
+                    d_box: node_box,
+                }
+            }
+        }
+    }
+}
+
+impl Traceable for BvhNode {
+    fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
+        render_stats::count_node_test();
+        if !self.d_box.intersects_ray(ray, min_trace, max_trace) {
+            return None;
+        }
+
+        let left_hit = self.d_left.intersects_ray(ray, min_trace, max_trace);
+        let closest_intersect = left_hit.as_ref().map_or(max_trace, |hit| hit.trace());
+
+        let right_hit = self
+            .d_right
+            .as_ref()
+            .and_then(|right| right.intersects_ray(ray, min_trace, closest_intersect));
+
+        right_hit.or(left_hit)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.d_box)
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::types::{hit_record::Point3, vec3::Vec3};
 use crate::utils::config::Config;
+use crate::utils::utilities::{degrees_to_radians, random_point_in_unit_disk, RandomGenerator};
 
 use super::ray::Ray;
 
@@ -11,40 +12,76 @@ pub struct Camera {
     d_lower_left_corner: Point3,
     d_horizontal: Vec3,
     d_vertical: Vec3,
+    d_u: Vec3,
+    d_v: Vec3,
+    d_lens_radius: f64,
 }
 
 impl Camera {
+    /// Create a camera at `look_from` pointed towards `look_at`, rolled so that `vup`
+    /// is upwards, with a vertical field of view in degrees. A non-zero aperture
+    /// (lens radius) blurs everything that is not at `focus_distance` from the camera
     pub fn new(
-        origin: &Point3,
-        viewport_width: u64,
-        viewport_height: u64,
-        focal_length: f64,
+        look_from: &Point3,
+        look_at: &Point3,
+        vup: &Vec3,
+        vertical_fov: f64,
+        aspect_ratio: f64,
+        aperture: f64,
+        focus_distance: f64,
     ) -> Self {
-        let _horizontal = Vec3::new(viewport_width as f64, 0.0, 0.0);
-        let _vertical = Vec3::new(0.0, viewport_height as f64, 0.0);
+        let viewport_height = 2.0 * (degrees_to_radians(vertical_fov) / 2.0).tan();
+        let viewport_width = aspect_ratio * viewport_height;
+
+        // Orthonormal basis for the camera orientation
+        let w = (*look_from - *look_at)
+            .unit_vector()
+            .expect("Camera look_from and look_at must be different points");
+        let u = vup
+            .cross(&w)
+            .unit_vector()
+            .expect("Camera vup must not be parallel to the viewing direction");
+        let v = w.cross(&u);
+
+        // Viewport lies on the plane of perfect focus
+        let _horizontal = u.scaled(viewport_width * focus_distance);
+        let _vertical = v.scaled(viewport_height * focus_distance);
 
         Camera {
-            d_origin: *origin,
-            d_lower_left_corner: *origin
-                - Vec3::new(_horizontal.x() / 2.0, _vertical.y() / 2.0, focal_length),
+            d_origin: *look_from,
+            d_lower_left_corner: *look_from
+                - _horizontal.scaled(0.5)
+                - _vertical.scaled(0.5)
+                - w.scaled(focus_distance),
             d_horizontal: _horizontal,
             d_vertical: _vertical,
+            d_u: u,
+            d_v: v,
+            d_lens_radius: aperture,
         }
     }
 
     pub fn configure(configuration: &Config) -> Self {
         Camera::new(
-            configuration.camera_config().origin(),
-            configuration.camera_config().viewport_width(),
-            configuration.camera_config().viewport_height(),
-            configuration.camera_config().focal_length(),
+            configuration.camera_config().look_from(),
+            configuration.camera_config().look_at(),
+            configuration.camera_config().vup(),
+            configuration.camera_config().vertical_fov(),
+            configuration.image_config().aspect_ratio(),
+            configuration.camera_config().aperture(),
+            configuration.camera_config().focus_distance(),
         )
     }
 
-    pub fn get_ray(&self, u: f64, v: f64) -> Ray {
+    pub fn get_ray(&self, u: f64, v: f64, rng: &mut RandomGenerator) -> Ray {
+        // Sample the ray origin on the lens disk for defocus blur
+        let lens_point = random_point_in_unit_disk(rng).scaled(self.d_lens_radius);
+        let origin =
+            self.d_origin + self.d_u.scaled(lens_point.x()) + self.d_v.scaled(lens_point.y());
+
         let direction =
             self.d_lower_left_corner + self.d_horizontal.scaled(u) + self.d_vertical.scaled(v)
-                - self.d_origin;
-        Ray::new(&self.d_origin, &direction)
+                - origin;
+        Ray::new(&origin, &direction)
     }
 }
@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+
+use crate::types::color::Color;
+use crate::utils::config::{Config, IntegratorMode};
+use crate::utils::render_stats;
+use crate::utils::utilities::{random_f64, RandomGenerator, MAX_F64};
+
+use super::{background::Background, ray::Ray, traceable::Traceable};
+
+/// Estimates the light arriving along a camera ray
+pub trait Integrator: Send + Sync {
+    fn radiance(
+        &self,
+        ray: &Ray,
+        scene_objects: &dyn Traceable,
+        background: &Background,
+        config: &Config,
+        rng: &mut RandomGenerator,
+    ) -> Color;
+}
+
+/// Create the integrator selected in the render configuration
+pub fn configure_integrator(configuration: &Config) -> Box<dyn Integrator> {
+    match configuration.render_config().integrator() {
+        IntegratorMode::Recursive => Box::new(RecursiveIntegrator {}),
+        IntegratorMode::Iterative => Box::new(IterativeIntegrator {}),
+    }
+}
+
+/// Decide whether a path continues past bounce number `bounces`, given the product of
+/// its attenuations. Returns the weight compensating for the paths stopped before it,
+/// or None if the path stops
+pub fn russian_roulette(
+    config: &Config,
+    bounces: u64,
+    throughput: &Color,
+    rng: &mut RandomGenerator,
+) -> Option<f64> {
+    let rays_config = config.rays_config();
+    if !rays_config.russian_roulette() || bounces <= rays_config.roulette_min_depth() {
+        return Some(1.0);
+    }
+
+    let survival = throughput
+        .x()
+        .max(throughput.y())
+        .max(throughput.z())
+        .min(1.0);
+    if random_f64(rng) >= survival {
+        return None;
+    }
+
+    Some(1.0 / survival)
+}
+
+/// Follows each scattered ray with a recursive call, combining the colors on the way back
+pub struct RecursiveIntegrator {}
+
+impl Integrator for RecursiveIntegrator {
+    fn radiance(
+        &self,
+        ray: &Ray,
+        scene_objects: &dyn Traceable,
+        background: &Background,
+        config: &Config,
+        rng: &mut RandomGenerator,
+    ) -> Color {
+        ray.ray_color(scene_objects, background, config, rng)
+    }
+}
+
+/// Follows the path in a loop, carrying its throughput and the radiance gathered so far.
+/// Draws the same random numbers as the recursive integrator, so the results match
+pub struct IterativeIntegrator {}
+
+impl Integrator for IterativeIntegrator {
+    fn radiance(
+        &self,
+        ray: &Ray,
+        scene_objects: &dyn Traceable,
+        background: &Background,
+        config: &Config,
+        rng: &mut RandomGenerator,
+    ) -> Color {
+        let mut radiance = Color::zero_vec();
+        // Product of the attenuations along the path, and of the Russian roulette weights
+        let mut throughput = Color::ones_vec();
+        let mut roulette_weight = 1.0;
+
+        let mut ray = Ray::new(ray.origin(), ray.direction());
+        for bounces in 0..config.rays_config().max_child_rays() {
+            render_stats::count_ray(bounces == 0);
+
+            let hit_record = match scene_objects.intersects_ray(&ray, 0.00001, MAX_F64) {
+                Some(hit_record) => hit_record,
+                None => {
+                    radiance +=
+                        (throughput * background.color(ray.direction())).scaled(roulette_weight);
+                    break;
+                }
+            };
+
+            radiance +=
+                (throughput * hit_record.material().emitted(&hit_record)).scaled(roulette_weight);
+
+            let (scattered_ray, attenuated_color) =
+                match hit_record
+                    .material()
+                    .scatter(&ray, &hit_record, config, rng)
+                {
+                    Some(scattered) => scattered,
+                    None => break,
+                };
+
+            throughput = throughput * attenuated_color;
+            match russian_roulette(config, bounces + 1, &throughput, rng) {
+                Some(weight) => roulette_weight *= weight,
+                None => break,
+            }
+
+            ray = scattered_ray;
+        }
+
+        radiance
+    }
+}
@@ -2,14 +2,18 @@
 
 use crate::{
     types::{
-        color::{Color, COLOR_BLACK, COLOR_WHITE},
+        color::{Color, COLOR_BLACK},
         hit_record::Point3,
         vec3::Vec3,
     },
-    utils::{config::Config, utilities::MAX_F64},
+    utils::{
+        config::Config,
+        render_stats,
+        utilities::{RandomGenerator, MAX_F64},
+    },
 };
 
-use super::traceable::{Traceable, TraceableGroup};
+use super::{background::Background, integrator::russian_roulette, traceable::Traceable};
 
 #[derive(Debug)]
 pub struct Ray {
@@ -37,49 +41,72 @@ impl Ray {
         self.d_origin + self.d_direction.scaled(t)
     }
 
-    pub fn ray_color(&self, scene_objects: &TraceableGroup, scene_config: &Config) -> Color {
+    pub fn ray_color(
+        &self,
+        scene_objects: &dyn Traceable,
+        background: &Background,
+        scene_config: &Config,
+        rng: &mut RandomGenerator,
+    ) -> Color {
         self.ray_color_internal(
             scene_objects,
+            background,
             scene_config,
+            rng,
             scene_config.rays_config().max_child_rays(),
+            Color::ones_vec(),
         )
     }
 
     fn ray_color_internal(
         &self,
-        scene_objects: &TraceableGroup,
+        scene_objects: &dyn Traceable,
+        background: &Background,
         scene_config: &Config,
+        rng: &mut RandomGenerator,
         depth: u64,
+        throughput: Color,
     ) -> Color {
-        if depth <= 0 {
+        if depth == 0 {
             return COLOR_BLACK;
         }
 
+        render_stats::count_ray(depth == scene_config.rays_config().max_child_rays());
+
         let hit_record_option = scene_objects.intersects_ray(self, 0.00001, MAX_F64);
         if let Some(hit_record) = hit_record_option {
-            if let Some((scattered_ray, attenuated_color)) =
+            let emitted_color = hit_record.material().emitted(&hit_record);
+
+            if let Some((scattered_ray, mut attenuated_color)) =
                 hit_record
                     .material()
-                    .scatter(self, &hit_record, scene_config)
+                    .scatter(self, &hit_record, scene_config, rng)
             {
-                return scattered_ray.ray_color_internal(scene_objects, scene_config, depth - 1)
-                    * attenuated_color;
+                let child_throughput = throughput * attenuated_color;
+
+                // Past the minimum depth, continue the path with a probability that
+                // follows its throughput, and weight surviving paths by its inverse so
+                // that the expected color is unchanged
+                let bounces = scene_config.rays_config().max_child_rays() - depth + 1;
+                match russian_roulette(scene_config, bounces, &child_throughput, rng) {
+                    Some(weight) => attenuated_color.scale(weight),
+                    None => return emitted_color,
+                };
+
+                return emitted_color
+                    + scattered_ray.ray_color_internal(
+                        scene_objects,
+                        background,
+                        scene_config,
+                        rng,
+                        depth - 1,
+                        child_throughput,
+                    ) * attenuated_color;
             } else {
-                return COLOR_BLACK;
+                return emitted_color;
             }
         }
 
-        let t = (self
-            .direction()
-            .unit_vector()
-            .expect("Weirdly, the ray is heading in the null direction")
-            .y()
-            + 1.0)
-            * 0.5;
-
-        let start_blend = COLOR_WHITE;
-        let end_blend = Color::new(0.5, 0.7, 1.0);
-
-        start_blend.scaled(1.0 - t) + end_blend.scaled(t)
+        background.color(self.direction())
     }
 }
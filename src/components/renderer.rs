@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::types::{
+    accumulation_buffer::{AccumulationBuffer, PixelAccumulator},
+    framebuffer::Framebuffer,
+};
+use crate::utils::config::Config;
+use crate::utils::render_stats::{self, ProgressReporter};
+use crate::utils::utilities::{entropy_seed, mix_seed, seeded_generator, RandomGenerator};
+
+use super::{
+    background::Background,
+    camera::Camera,
+    integrator::{configure_integrator, Integrator},
+    sampler::{configure_sampler, Sampler},
+    traceable::Traceable,
+};
+
+// Floor on the pixel brightness used for the adaptive sampling threshold, so that
+// nearly black pixels do not need to become perfectly noise free
+const MIN_RELATIVE_LUMINANCE: f64 = 0.01;
+
+/// Renders the scene one scanline at a time, spread across a pool of threads
+pub struct Renderer<'a> {
+    d_camera: &'a Camera,
+    d_scene_objects: &'a dyn Traceable,
+    d_background: &'a Background,
+    d_config: &'a Config,
+    d_integrator: Box<dyn Integrator>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(
+        camera: &'a Camera,
+        scene_objects: &'a dyn Traceable,
+        background: &'a Background,
+        config: &'a Config,
+    ) -> Self {
+        Renderer {
+            d_camera: camera,
+            d_scene_objects: scene_objects,
+            d_background: background,
+            d_config: config,
+            d_integrator: configure_integrator(config),
+        }
+    }
+
+    /// Render the image into a framebuffer of averaged pixel samples
+    pub fn render(&self) -> Framebuffer {
+        self.render_with_sample_counts().0
+    }
+
+    /// Render the image, along with a grayscale image of the number of samples each
+    /// pixel took, relative to the maximum number of samples per pixel
+    pub fn render_with_sample_counts(&self) -> (Framebuffer, Framebuffer) {
+        let max_samples = self.d_config.image_config().samples_per_pixel();
+
+        let mut buffer = self.create_buffer();
+        self.render_pass(&mut buffer, max_samples);
+
+        (buffer.to_framebuffer(), buffer.sample_counts(max_samples))
+    }
+
+    /// Empty accumulation buffer for the configured image, seeded from the config
+    pub fn create_buffer(&self) -> AccumulationBuffer {
+        let image_config = self.d_config.image_config();
+        let seed = self
+            .d_config
+            .render_config()
+            .seed()
+            .unwrap_or_else(entropy_seed);
+
+        AccumulationBuffer::new(
+            image_config.image_width() as usize,
+            image_config.image_height() as usize,
+            seed,
+        )
+    }
+
+    /// Keep adding passes of `pass_samples` samples per pixel to the buffer until every
+    /// pixel has reached the configured number of samples, calling `on_pass` after each
+    /// pass. A buffer loaded from a previous render continues where it stopped
+    pub fn render_progressive(
+        &self,
+        buffer: &mut AccumulationBuffer,
+        pass_samples: u64,
+        mut on_pass: impl FnMut(&AccumulationBuffer),
+    ) {
+        let max_samples = self.d_config.image_config().samples_per_pixel();
+
+        while buffer.target_samples() < max_samples {
+            let target_samples = (buffer.target_samples() + pass_samples.max(1)).min(max_samples);
+            self.render_pass(buffer, target_samples);
+            on_pass(buffer);
+        }
+    }
+
+    /// Sample every pixel of the buffer until it has `target_samples` samples or, with
+    /// adaptive sampling, has converged
+    pub fn render_pass(&self, buffer: &mut AccumulationBuffer, target_samples: u64) {
+        let image_height = buffer.height() as u64;
+        let seed = buffer.seed();
+        let pass = buffer.passes();
+
+        let sampler = configure_sampler(self.d_config, seed);
+        let progress = ProgressReporter::new(
+            &format!("Pass {}", pass + 1),
+            image_height,
+            self.d_config.render_config().show_progress(),
+        );
+
+        let next_row = AtomicU64::new(0);
+        let shared_buffer = Mutex::new(&mut *buffer);
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.d_config.render_config().threads() {
+                scope.spawn(|| loop {
+                    let row = next_row.fetch_add(1, Ordering::Relaxed);
+                    if row >= image_height {
+                        render_stats::flush_thread_counters();
+                        break;
+                    }
+
+                    // Each scanline of each pass has its own random sequence, so the
+                    // output does not depend on which thread renders it
+                    let mut rng = seeded_generator(mix_seed(mix_seed(seed, pass), row));
+
+                    let mut row_pixels = shared_buffer.lock().unwrap().row(row as usize).to_vec();
+
+                    let j = image_height - 1 - row;
+                    for (i, pixel) in row_pixels.iter_mut().enumerate() {
+                        self.render_pixel(
+                            i as u64,
+                            j,
+                            pixel,
+                            target_samples,
+                            sampler.as_ref(),
+                            &mut rng,
+                        );
+                    }
+
+                    shared_buffer
+                        .lock()
+                        .unwrap()
+                        .row_mut(row as usize)
+                        .copy_from_slice(&row_pixels);
+                    progress.row_done();
+                });
+            }
+        });
+
+        buffer.finish_pass(target_samples);
+    }
+
+    /// Add samples to a pixel until it has `target_samples` samples or has converged
+    fn render_pixel(
+        &self,
+        i: u64,
+        j: u64,
+        pixel: &mut PixelAccumulator,
+        target_samples: u64,
+        sampler: &dyn Sampler,
+        rng: &mut RandomGenerator,
+    ) {
+        let image_config = self.d_config.image_config();
+        let max_samples = image_config.samples_per_pixel();
+        let min_samples = image_config.min_samples_per_pixel();
+
+        while pixel.samples() < target_samples.min(max_samples) {
+            if let Some(threshold) = image_config.noise_threshold() {
+                // Standard error of the mean, relative to the pixel brightness
+                if pixel.samples() >= min_samples
+                    && pixel.standard_error()
+                        <= threshold * pixel.luminance_mean().max(MIN_RELATIVE_LUMINANCE)
+                {
+                    break;
+                }
+            }
+
+            let (offset_u, offset_v) =
+                sampler.pixel_sample(i, j, pixel.samples(), max_samples, rng);
+            let u = (i as f64 + offset_u) / (image_config.image_width() as f64 - 1.0);
+            let v = (j as f64 + offset_v) / (image_config.image_height() as f64 - 1.0);
+
+            let r = self.d_camera.get_ray(u, v, rng);
+            pixel.add_sample(&self.d_integrator.radiance(
+                &r,
+                self.d_scene_objects,
+                self.d_background,
+                self.d_config,
+                rng,
+            ));
+        }
+    }
+}
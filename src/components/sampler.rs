@@ -0,0 +1,228 @@
+#![allow(dead_code)]
+
+use crate::utils::config::{AntialiasingMode, Config, SamplerMode};
+use crate::utils::utilities::{mix_seed, random_f64, RandomGenerator};
+
+/// Chooses where in a pixel each of its samples is taken
+pub trait Sampler: Send + Sync {
+    /// Position in [0, 1) x [0, 1) within pixel (x, y) of sample `index` out of `count`
+    fn pixel_sample(
+        &self,
+        x: u64,
+        y: u64,
+        index: u64,
+        count: u64,
+        rng: &mut RandomGenerator,
+    ) -> (f64, f64);
+}
+
+/// Create the sampler for the antialiasing mode and sampler selected in the image
+/// configuration. The seed decorrelates the low-discrepancy sequences of neighbouring pixels
+pub fn configure_sampler(configuration: &Config, seed: u64) -> Box<dyn Sampler> {
+    match configuration.image_config().aa_mode() {
+        AntialiasingMode::None {} => return Box::new(CornerSampler {}),
+        AntialiasingMode::Grid { .. } => return Box::new(GridSampler {}),
+        AntialiasingMode::MSAA { .. } | AntialiasingMode::Adaptive { .. } => {}
+    }
+
+    match configuration.image_config().sampler() {
+        SamplerMode::Independent => Box::new(IndependentSampler {}),
+        SamplerMode::Stratified => Box::new(StratifiedSampler {}),
+        SamplerMode::Halton => Box::new(HaltonSampler::new(seed)),
+        SamplerMode::Sobol => Box::new(SobolSampler::new(seed)),
+    }
+}
+
+/// Always the corner of the pixel, for rendering without antialiasing
+pub struct CornerSampler {}
+
+impl Sampler for CornerSampler {
+    fn pixel_sample(
+        &self,
+        _x: u64,
+        _y: u64,
+        _index: u64,
+        _count: u64,
+        _rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+/// Centers of the cells of a square grid over the pixel, for `count` square numbers
+pub struct GridSampler {}
+
+impl Sampler for GridSampler {
+    fn pixel_sample(
+        &self,
+        _x: u64,
+        _y: u64,
+        index: u64,
+        count: u64,
+        _rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        let grid_size = ((count as f64).sqrt().round() as u64).max(1);
+
+        (
+            ((index % grid_size) as f64 + 0.5) / grid_size as f64,
+            ((index / grid_size) as f64 + 0.5) / grid_size as f64,
+        )
+    }
+}
+
+/// Uniformly random positions, independent of each other
+pub struct IndependentSampler {}
+
+impl Sampler for IndependentSampler {
+    fn pixel_sample(
+        &self,
+        _x: u64,
+        _y: u64,
+        _index: u64,
+        _count: u64,
+        rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        (random_f64(rng), random_f64(rng))
+    }
+}
+
+/// One random position in each cell of a square grid over the pixel. Samples that do
+/// not fit in the largest grid with at most `count` cells are independent. Cells are
+/// visited in a scattered order, so any prefix of the samples covers the whole pixel
+pub struct StratifiedSampler {}
+
+impl Sampler for StratifiedSampler {
+    fn pixel_sample(
+        &self,
+        _x: u64,
+        _y: u64,
+        index: u64,
+        count: u64,
+        rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        let grid_size = (count as f64).sqrt().floor() as u64;
+        if index >= grid_size * grid_size {
+            return (random_f64(rng), random_f64(rng));
+        }
+
+        // Step through the cells by a stride near the golden ratio of the cell count.
+        // Being coprime with the cell count, it still visits every cell once
+        let cell_count = grid_size * grid_size;
+        let mut stride = ((cell_count as f64 * 0.618) as u64).max(1);
+        while greatest_common_divisor(stride, cell_count) != 1 {
+            stride += 1;
+        }
+        let cell = (index * stride) % cell_count;
+
+        let cell_x = (cell % grid_size) as f64;
+        let cell_y = (cell / grid_size) as f64;
+        (
+            (cell_x + random_f64(rng)) / grid_size as f64,
+            (cell_y + random_f64(rng)) / grid_size as f64,
+        )
+    }
+}
+
+/// Halton sequence in bases 2 and 3, randomly shifted per pixel
+pub struct HaltonSampler {
+    d_seed: u64,
+}
+
+impl HaltonSampler {
+    pub fn new(seed: u64) -> Self {
+        HaltonSampler { d_seed: seed }
+    }
+
+    /// Digits of the index in the given base, mirrored around the decimal point
+    pub fn radical_inverse(base: u64, mut index: u64) -> f64 {
+        let inverse_base = 1.0 / base as f64;
+        let mut digit_weight = inverse_base;
+        let mut result = 0.0;
+
+        while index > 0 {
+            result += (index % base) as f64 * digit_weight;
+            index /= base;
+            digit_weight *= inverse_base;
+        }
+
+        result
+    }
+}
+
+impl Sampler for HaltonSampler {
+    fn pixel_sample(
+        &self,
+        x: u64,
+        y: u64,
+        index: u64,
+        _count: u64,
+        _rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        // Wrapping shift (Cranley-Patterson rotation) keeps the points evenly spread
+        let shift = pixel_hash(self.d_seed, x, y);
+        let shift_x = (shift >> 11) as f64 / (1u64 << 53) as f64;
+        let shift_y = (mix_seed(shift, 1) >> 11) as f64 / (1u64 << 53) as f64;
+
+        (
+            (HaltonSampler::radical_inverse(2, index) + shift_x).fract(),
+            (HaltonSampler::radical_inverse(3, index) + shift_y).fract(),
+        )
+    }
+}
+
+/// First two dimensions of the Sobol sequence, XOR scrambled per pixel
+pub struct SobolSampler {
+    d_seed: u64,
+}
+
+impl SobolSampler {
+    pub fn new(seed: u64) -> Self {
+        SobolSampler { d_seed: seed }
+    }
+
+    /// Second Sobol dimension as 32-bit fixed point. The first is the bit reversed index
+    fn second_dimension(mut index: u32) -> u32 {
+        let mut direction: u32 = 1 << 31;
+        let mut result = 0;
+
+        while index != 0 {
+            if index & 1 != 0 {
+                result ^= direction;
+            }
+            index >>= 1;
+            direction ^= direction >> 1;
+        }
+
+        result
+    }
+}
+
+impl Sampler for SobolSampler {
+    fn pixel_sample(
+        &self,
+        x: u64,
+        y: u64,
+        index: u64,
+        _count: u64,
+        _rng: &mut RandomGenerator,
+    ) -> (f64, f64) {
+        // Scrambling the bits keeps each power of two of samples stratified
+        let scramble = pixel_hash(self.d_seed, x, y);
+        let index = index as u32;
+        let sample_x = index.reverse_bits() ^ scramble as u32;
+        let sample_y = SobolSampler::second_dimension(index) ^ (scramble >> 32) as u32;
+
+        (sample_x as f64 / (1u64 << 32) as f64, sample_y as f64 / (1u64 << 32) as f64)
+    }
+}
+
+fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn pixel_hash(seed: u64, x: u64, y: u64) -> u64 {
+    mix_seed(mix_seed(seed, x), y)
+}
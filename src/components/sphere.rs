@@ -1,4 +1,5 @@
-use crate::types::{hit_record::HitRecord, material::Material, vec3::Vec3};
+use crate::types::{aabb::Aabb, hit_record::HitRecord, material::Material, vec3::Vec3};
+use crate::utils::{render_stats, utilities::PI};
 
 use super::{ray::Ray, traceable::Traceable};
 
@@ -9,7 +10,11 @@ pub struct Sphere {
 }
 
 impl Sphere {
-    pub fn new(center: &Vec3, radius: f64, material: Box<dyn Material>) -> Result<Self, &str> {
+    pub fn new(
+        center: &Vec3,
+        radius: f64,
+        material: Box<dyn Material>,
+    ) -> Result<Self, &'static str> {
         if radius <= 0.0 {
             return Err("Sphere radius must be greater than 0");
         }
@@ -28,10 +33,20 @@ impl Sphere {
     pub fn radius(&self) -> f64 {
         self.d_radius
     }
+
+    /// Texture coordinates of a point on the unit sphere. u goes around the Y axis
+    /// starting from -X, v goes from the bottom (-Y) to the top (+Y)
+    pub fn surface_uv(point: &Vec3) -> (f64, f64) {
+        let theta = (-point.y()).clamp(-1.0, 1.0).acos();
+        let phi = (-point.z()).atan2(point.x()) + PI;
+
+        (phi / (2.0 * PI), theta / PI)
+    }
 }
 
 impl Traceable for Sphere {
     fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
+        render_stats::count_object_test();
         let ray_trace = *ray.origin() - *self.center();
         let a = ray.direction().len_squared();
         let b = ray_trace.dot(ray.direction());
@@ -54,8 +69,20 @@ impl Traceable for Sphere {
         let _point = ray.at(root);
         let _normal = (_point - *self.center()).scaled(1.0 / self.radius());
 
-        let mut hit_record = HitRecord::new(root, _point, _normal, false, &self.d_material);
+        let mut hit_record = HitRecord::new(
+            root,
+            _point,
+            _normal,
+            Sphere::surface_uv(&_normal),
+            false,
+            self.d_material.as_ref(),
+        );
         hit_record.set_face_normal(ray, &_normal);
         Some(hit_record)
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = Vec3::ones_vec().scaled(self.radius());
+        Some(Aabb::new(&(*self.center() - extent), &(*self.center() + extent)))
+    }
 }
@@ -1,11 +1,14 @@
 #![allow(dead_code)]
 
-use crate::types::hit_record::HitRecord;
+use crate::types::{aabb::Aabb, hit_record::HitRecord};
 
 use super::ray::Ray;
 
-pub trait Traceable {
+pub trait Traceable: Send + Sync {
     fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord>;
+
+    /// Box enclosing the object, or None if the object is unbounded
+    fn bounding_box(&self) -> Option<Aabb>;
 }
 
 pub struct TraceableGroup {
@@ -14,26 +17,33 @@ pub struct TraceableGroup {
 
 impl Traceable for TraceableGroup {
     fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
-        if self.objects.len() == 0 {
+        if self.objects.is_empty() {
             return None;
         }
 
         let mut hit_record: Option<HitRecord> = None;
-
-        let mut any_intersect = false;
         let mut closest_intersect = max_trace;
 
         for object in &self.objects {
-            let this_hit_record = object.intersects_ray(ray, min_trace, closest_intersect);
-            if this_hit_record.is_some() {
-                any_intersect = true;
-                closest_intersect = this_hit_record.as_ref().unwrap().trace();
-
-                hit_record = this_hit_record;
+            if let Some(this_hit_record) = object.intersects_ray(ray, min_trace, closest_intersect)
+            {
+                closest_intersect = this_hit_record.trace();
+                hit_record = Some(this_hit_record);
             }
         }
 
-        return if any_intersect { hit_record } else { None };
+        hit_record
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut objects = self.objects.iter();
+        let mut group_box = objects.next()?.bounding_box()?;
+
+        for object in objects {
+            group_box = Aabb::surrounding_box(&group_box, &object.bounding_box()?);
+        }
+
+        Some(group_box)
     }
 }
 
@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+
+use crate::types::{
+    aabb::Aabb,
+    hit_record::{HitRecord, Point3},
+    material::Material,
+    vec3::Vec3,
+};
+use crate::utils::render_stats;
+
+use super::{ray::Ray, traceable::Traceable};
+
+// Rays closer than this to parallel with the triangle plane are treated as misses
+const PARALLEL_EPSILON: f64 = 1e-12;
+// Padding so that axis-aligned triangles still have a box with non-zero thickness
+const BOX_PADDING: f64 = 1e-8;
+
+pub struct Triangle {
+    d_vertices: [Point3; 3],
+    d_normals: Option<[Vec3; 3]>,
+    d_texture_coords: Option<[(f64, f64); 3]>,
+    d_material: Box<dyn Material>,
+}
+
+impl Triangle {
+    /// Create a flat shaded triangle. Vertices are wound counter-clockwise around
+    /// the front face
+    pub fn new(vertices: [Point3; 3], material: Box<dyn Material>) -> Result<Self, &'static str> {
+        if triangle_normal(&vertices).is_none() {
+            return Err("Triangle vertices must not be collinear");
+        }
+
+        Ok(Triangle {
+            d_vertices: vertices,
+            d_normals: None,
+            d_texture_coords: None,
+            d_material: material,
+        })
+    }
+
+    /// Create a smooth shaded triangle, interpolating the given per-vertex normals
+    pub fn with_normals(
+        vertices: [Point3; 3],
+        normals: [Vec3; 3],
+        material: Box<dyn Material>,
+    ) -> Result<Self, &'static str> {
+        let mut triangle = Triangle::new(vertices, material)?;
+        triangle.d_normals = Some(normals);
+        Ok(triangle)
+    }
+
+    pub fn vertices(&self) -> &[Point3; 3] {
+        &self.d_vertices
+    }
+
+    pub fn normals(&self) -> Option<&[Vec3; 3]> {
+        self.d_normals.as_ref()
+    }
+
+    pub fn texture_coords(&self) -> Option<&[(f64, f64); 3]> {
+        self.d_texture_coords.as_ref()
+    }
+
+    /// Set the (u, v) texture coordinates of the three vertices
+    pub fn set_texture_coords(&mut self, texture_coords: [(f64, f64); 3]) {
+        self.d_texture_coords = Some(texture_coords);
+    }
+}
+
+impl Traceable for Triangle {
+    fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
+        render_stats::count_object_test();
+        let (trace, barycentric) = intersect_triangle(ray, &self.d_vertices, min_trace, max_trace)?;
+
+        Some(triangle_hit_record(
+            ray,
+            trace,
+            &self.d_vertices,
+            self.d_normals.as_ref(),
+            self.d_texture_coords.as_ref(),
+            &barycentric,
+            self.d_material.as_ref(),
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(triangle_bounding_box(&self.d_vertices))
+    }
+}
+
+/// Unit normal of the front face, or None for degenerate triangles
+pub fn triangle_normal(vertices: &[Point3; 3]) -> Option<Vec3> {
+    (vertices[1] - vertices[0])
+        .cross(&(vertices[2] - vertices[0]))
+        .unit_vector()
+        .ok()
+}
+
+/// Möller-Trumbore ray-triangle intersection. Returns the ray trace of the hit and
+/// the barycentric weights of the three vertices at the hit point
+pub fn intersect_triangle(
+    ray: &Ray,
+    vertices: &[Point3; 3],
+    min_trace: f64,
+    max_trace: f64,
+) -> Option<(f64, [f64; 3])> {
+    let edge_1 = vertices[1] - vertices[0];
+    let edge_2 = vertices[2] - vertices[0];
+
+    let p = ray.direction().cross(&edge_2);
+    let determinant = edge_1.dot(&p);
+    if determinant.abs() < PARALLEL_EPSILON {
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+
+    let s = *ray.origin() - vertices[0];
+    let u = s.dot(&p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(&edge_1);
+    let v = ray.direction().dot(&q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let trace = edge_2.dot(&q) * inverse_determinant;
+    if trace < min_trace || max_trace < trace {
+        return None;
+    }
+
+    Some((trace, [1.0 - u - v, u, v]))
+}
+
+/// Build the hit record for a triangle hit, interpolating vertex normals and texture
+/// coordinates if present. Without texture coordinates, u and v are the barycentric
+/// weights of the second and third vertex
+pub fn triangle_hit_record(
+    ray: &Ray,
+    trace: f64,
+    vertices: &[Point3; 3],
+    normals: Option<&[Vec3; 3]>,
+    texture_coords: Option<&[(f64, f64); 3]>,
+    barycentric: &[f64; 3],
+    material: &dyn Material,
+) -> HitRecord {
+    let geometric_normal = triangle_normal(vertices).unwrap_or_else(Vec3::zero_vec);
+
+    let outward_normal = match normals {
+        Some(normals) => {
+            let interpolated = (normals[0].scaled(barycentric[0])
+                + normals[1].scaled(barycentric[1])
+                + normals[2].scaled(barycentric[2]))
+            .unit_vector()
+            .unwrap_or(geometric_normal);
+
+            // Keep the shading normal on the same side as the front face
+            if interpolated.dot(&geometric_normal) < 0.0 {
+                -interpolated
+            } else {
+                interpolated
+            }
+        }
+        None => geometric_normal,
+    };
+
+    let uv = match texture_coords {
+        Some(texture_coords) => (
+            texture_coords
+                .iter()
+                .zip(barycentric)
+                .map(|((u, _), weight)| u * weight)
+                .sum(),
+            texture_coords
+                .iter()
+                .zip(barycentric)
+                .map(|((_, v), weight)| v * weight)
+                .sum(),
+        ),
+        None => (barycentric[1], barycentric[2]),
+    };
+
+    let mut hit_record = HitRecord::new(trace, ray.at(trace), outward_normal, uv, false, material);
+    hit_record.set_face_normal(ray, &outward_normal);
+    hit_record
+}
+
+pub fn triangle_bounding_box(vertices: &[Point3; 3]) -> Aabb {
+    let padding = Vec3::ones_vec().scaled(BOX_PADDING);
+    let vertex_box = |vertex: &Point3| Aabb::new(&(*vertex - padding), &(*vertex + padding));
+
+    Aabb::surrounding_box(
+        &Aabb::surrounding_box(&vertex_box(&vertices[0]), &vertex_box(&vertices[1])),
+        &vertex_box(&vertices[2]),
+    )
+}
@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use crate::types::{
+    aabb::Aabb,
+    hit_record::{HitRecord, Point3},
+    material::Material,
+    vec3::Vec3,
+};
+use crate::utils::render_stats;
+
+use super::{
+    bvh::BvhNode,
+    ray::Ray,
+    traceable::{Traceable, TraceableGroup},
+    triangle::{intersect_triangle, triangle_bounding_box, triangle_hit_record},
+};
+
+/// Vertex and index buffers shared by all triangles of a mesh
+struct MeshData {
+    vertices: Vec<Point3>,
+    normals: Option<Vec<Vec3>>,
+    texture_coords: Option<Vec<(f64, f64)>>,
+    indices: Vec<[usize; 3]>,
+    material: Box<dyn Material>,
+}
+
+impl MeshData {
+    fn face_vertices(&self, face: usize) -> [Point3; 3] {
+        let [a, b, c] = self.indices[face];
+        [self.vertices[a], self.vertices[b], self.vertices[c]]
+    }
+
+    fn face_normals(&self, face: usize) -> Option<[Vec3; 3]> {
+        let [a, b, c] = self.indices[face];
+        self.normals
+            .as_ref()
+            .map(|normals| [normals[a], normals[b], normals[c]])
+    }
+
+    fn face_texture_coords(&self, face: usize) -> Option<[(f64, f64); 3]> {
+        let [a, b, c] = self.indices[face];
+        self.texture_coords
+            .as_ref()
+            .map(|texture_coords| [texture_coords[a], texture_coords[b], texture_coords[c]])
+    }
+}
+
+/// Single face of a mesh, looked up in the shared buffers
+struct MeshTriangle {
+    d_mesh: Arc<MeshData>,
+    d_face: usize,
+}
+
+impl Traceable for MeshTriangle {
+    fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
+        render_stats::count_object_test();
+        let vertices = self.d_mesh.face_vertices(self.d_face);
+        let (trace, barycentric) = intersect_triangle(ray, &vertices, min_trace, max_trace)?;
+
+        Some(triangle_hit_record(
+            ray,
+            trace,
+            &vertices,
+            self.d_mesh.face_normals(self.d_face).as_ref(),
+            self.d_mesh.face_texture_coords(self.d_face).as_ref(),
+            &barycentric,
+            self.d_mesh.material.as_ref(),
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(triangle_bounding_box(&self.d_mesh.face_vertices(self.d_face)))
+    }
+}
+
+/// Triangles sharing vertex, normal and index buffers and a single material.
+/// Faces are kept in their own BVH so large meshes stay cheap to trace
+pub struct TriangleMesh {
+    d_mesh: Arc<MeshData>,
+    d_faces: BvhNode,
+}
+
+impl TriangleMesh {
+    /// Create a mesh from triangles indexing into the vertex buffer. If normals are
+    /// given, there must be one per vertex and the mesh is smooth shaded
+    pub fn new(
+        vertices: Vec<Point3>,
+        normals: Option<Vec<Vec3>>,
+        indices: Vec<[usize; 3]>,
+        material: Box<dyn Material>,
+    ) -> Result<Self, &'static str> {
+        TriangleMesh::with_texture_coords(vertices, normals, None, indices, material)
+    }
+
+    /// Create a mesh with optional (u, v) texture coordinates, one pair per vertex
+    pub fn with_texture_coords(
+        vertices: Vec<Point3>,
+        normals: Option<Vec<Vec3>>,
+        texture_coords: Option<Vec<(f64, f64)>>,
+        indices: Vec<[usize; 3]>,
+        material: Box<dyn Material>,
+    ) -> Result<Self, &'static str> {
+        if indices.is_empty() {
+            return Err("Mesh must have at least one triangle");
+        }
+
+        if indices
+            .iter()
+            .flatten()
+            .any(|&index| index >= vertices.len())
+        {
+            return Err("Mesh triangle index is out of range of the vertex buffer");
+        }
+
+        if let Some(normals) = normals.as_ref() {
+            if normals.len() != vertices.len() {
+                return Err("Mesh must have exactly one normal per vertex");
+            }
+        }
+
+        if let Some(texture_coords) = texture_coords.as_ref() {
+            if texture_coords.len() != vertices.len() {
+                return Err("Mesh must have exactly one texture coordinate per vertex");
+            }
+        }
+
+        let mesh = Arc::new(MeshData {
+            vertices,
+            normals,
+            texture_coords,
+            indices,
+            material,
+        });
+
+        let mut faces = TraceableGroup { objects: vec![] };
+        for face in 0..mesh.indices.len() {
+            faces.add(Box::new(MeshTriangle {
+                d_mesh: mesh.clone(),
+                d_face: face,
+            }));
+        }
+
+        Ok(TriangleMesh {
+            d_faces: BvhNode::from_group(faces)?,
+            d_mesh: mesh,
+        })
+    }
+
+    pub fn vertices(&self) -> &[Point3] {
+        &self.d_mesh.vertices
+    }
+
+    pub fn normals(&self) -> Option<&[Vec3]> {
+        self.d_mesh.normals.as_deref()
+    }
+
+    pub fn texture_coords(&self) -> Option<&[(f64, f64)]> {
+        self.d_mesh.texture_coords.as_deref()
+    }
+
+    pub fn indices(&self) -> &[[usize; 3]] {
+        &self.d_mesh.indices
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.d_mesh.indices.len()
+    }
+}
+
+impl Traceable for TriangleMesh {
+    fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> Option<HitRecord> {
+        self.d_faces.intersects_ray(ray, min_trace, max_trace)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.d_faces.bounding_box()
+    }
+}
@@ -3,71 +3,210 @@ pub mod tests;
 mod types;
 mod utils;
 
-use types::color;
-use types::vec3::Vec3;
+use std::path::Path;
+use std::time::Instant;
+
 use utils::config::Config;
 
-use crate::components::{camera::Camera, sphere::Sphere, traceable::TraceableGroup};
-use crate::types::color::Color;
-use crate::types::dielectric_mat::{DielectricMaterial, IOR_GLASS};
-use crate::types::diffuse_mat::DiffuseMaterial;
-use crate::types::metal_mat::MetalMaterial;
-use crate::utils::config::AntialiasingMode;
-use crate::utils::utilities::random_f64;
+use crate::components::{
+    background::Background, bvh::BvhNode, camera::Camera, renderer::Renderer, traceable::Traceable,
+};
+use crate::types::{accumulation_buffer::AccumulationBuffer, framebuffer::Framebuffer};
+use crate::utils::cli::{CliArgs, USAGE};
+use crate::utils::config::{AccelerationStructure, ConfigError, ImageFormat};
+use crate::utils::config_builder::ConfigBuilder;
+use crate::utils::image_writer::write_image;
+use crate::utils::render_stats::RenderStats;
+use crate::utils::scene::SceneDescription;
 
 fn main() {
-    // Initial configuration object
-    let config: Config = Config::from_toml("settings.toml");
+    let cli_args = CliArgs::parse(std::env::args().skip(1)).unwrap_or_else(|cli_error| {
+        eprintln!("{}\n\n{}", cli_error, USAGE);
+        std::process::exit(2);
+    });
+
+    if cli_args.help() {
+        println!("{}", USAGE);
+        return;
+    }
+
+    // Initial configuration object, with the command line overrides applied
+    let mut config: Config = load_config(&cli_args);
+    cli_args.apply(&mut config);
+
+    if cli_args.dump_config() {
+        print!("{}", config.to_toml_string());
+        return;
+    }
 
     eprintln!("Using config: {:?}", &config);
 
     let camera = Camera::configure(&config);
+    // Assets are found relative to the scene file
+    let scene_directory = Path::new(cli_args.scene_path())
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    let background =
+        Background::configure_relative_to(&config, scene_directory).unwrap_or_else(|hdr_error| {
+            eprintln!("Unable to load background: {}", hdr_error);
+            std::process::exit(1);
+        });
 
     // Create scene objects
-    let ground_material = Box::new(DiffuseMaterial::new(&Color::new(0.8, 0.8, 0.0)));
-    let center_material = Box::new(DiffuseMaterial::new(&Color::new(0.7, 0.3, 0.3)));
-    let left_material = Box::new(MetalMaterial::new(&Color::new(0.8, 0.8, 0.8), 0.2));
-    let right_material = Box::new(DielectricMaterial::new(IOR_GLASS));
-
-    let scene_objects = TraceableGroup {
-        objects: vec![
-            Box::new(Sphere::new(&Vec3::new(0.0, -100.5, -1.0), 100.0, ground_material).unwrap()),
-            Box::new(Sphere::new(&Vec3::new(0.0, 0.0, -1.0), 0.5, center_material).unwrap()),
-            Box::new(Sphere::new(&Vec3::new(-1.5, 0.0, -1.0), 0.5, left_material).unwrap()),
-            Box::new(Sphere::new(&Vec3::new(1.5, 0.0, -1.0), 0.5, right_material).unwrap()),
-        ],
+    let scene_objects = SceneDescription::from_toml(cli_args.scene_path())
+        .and_then(|scene_description| scene_description.build())
+        .unwrap_or_else(|scene_error| {
+            eprintln!("Unable to load scene: {}", scene_error);
+            std::process::exit(1);
+        });
+
+    let scene_objects: Box<dyn Traceable> = match config.render_config().acceleration() {
+        // An empty scene has nothing to accelerate and renders only the background
+        AccelerationStructure::BVH if !scene_objects.objects.is_empty() => {
+            Box::new(BvhNode::from_group(scene_objects).unwrap_or_else(|bvh_error| {
+                eprintln!("Unable to build BVH: {}", bvh_error);
+                std::process::exit(1);
+            }))
+        }
+        _ => Box::new(scene_objects),
     };
 
-    println!(
-        "P3\n{},{}\n255",
-        config.image_config().image_width(),
-        config.image_config().image_height()
+    let renderer = Renderer::new(&camera, scene_objects.as_ref(), &background, &config);
+    let image_config = config.image_config();
+    let max_samples = image_config.samples_per_pixel();
+
+    let render_start = Instant::now();
+    let buffer = match config.render_config().progressive() {
+        Some(progressive) => {
+            let accumulation_path = progressive.accumulation_path();
+            let mut buffer = resume_buffer(&renderer, &config, accumulation_path);
+
+            renderer.render_progressive(&mut buffer, progressive.pass_samples(), |buffer| {
+                write_output(
+                    &buffer.to_framebuffer(),
+                    image_config.output_path(),
+                    image_config.output_format(),
+                    image_config.gamma(),
+                );
+                if let Some(accumulation_path) = accumulation_path {
+                    buffer.save(accumulation_path).unwrap_or_else(|io_error| {
+                        eprintln!("Unable to save '{}': {}", accumulation_path, io_error);
+                        std::process::exit(1);
+                    });
+                }
+                eprintln!(
+                    "Pass {} done: {}/{} samples per pixel",
+                    buffer.passes(),
+                    buffer.target_samples(),
+                    max_samples
+                );
+            });
+            buffer
+        }
+        None => {
+            let mut buffer = renderer.create_buffer();
+            renderer.render_pass(&mut buffer, max_samples);
+            buffer
+        }
+    };
+
+    eprintln!("{}", RenderStats::collect(render_start.elapsed()));
+
+    if let Some(sample_count_path) = image_config.sample_count_path() {
+        write_output(
+            &buffer.sample_counts(max_samples),
+            sample_count_path,
+            ImageFormat::from_path(sample_count_path),
+            1.0,
+        );
+        eprintln!("Wrote sample counts to {}", sample_count_path);
+    }
+
+    write_output(
+        &buffer.to_framebuffer(),
+        image_config.output_path(),
+        image_config.output_format(),
+        image_config.gamma(),
     );
 
-    for j in (0..config.image_config().image_height()).rev() {
-        for i in 0..config.image_config().image_width() {
-            let mut pixel_color = Color::zero_vec();
-            let aa_multiplier = if *config.image_config().aa_mode() == AntialiasingMode::MSAA {
-                1.0
-            } else {
-                0.0
-            };
-            for _ in 0..config.image_config().samples_per_pixel() {
-                let u = (i as f64 + (aa_multiplier * random_f64()))
-                    / (config.image_config().image_width() as f64 - 1.0);
-                let v = (j as f64 + (aa_multiplier * random_f64()))
-                    / (config.image_config().image_height() as f64 - 1.0);
-
-                let r = camera.get_ray(u, v);
-                pixel_color += r.ray_color(&scene_objects, &config);
+    eprintln!("Wrote image to {}", image_config.output_path());
+}
+
+/// Layer the settings file, the scene settings and the environment over the defaults.
+/// Layers with invalid settings are ignored, or stop the program in strict mode
+fn load_config(cli_args: &CliArgs) -> Config {
+    let check = |result: Result<(), ConfigError>| {
+        if let Err(config_error) = result {
+            if cli_args.strict() {
+                eprintln!("{}", config_error);
+                std::process::exit(1);
             }
+            eprintln!("{}. Ignoring these settings", config_error);
+        }
+    };
 
-            color::print_color(
-                &pixel_color,
-                config.image_config().samples_per_pixel(),
-                config.image_config().gamma(),
-            );
+    let mut builder = ConfigBuilder::new();
+    check(builder.add_file(cli_args.config_path()));
+    check(builder.add_scene_settings(cli_args.scene_path()));
+    for config_error in builder.add_env(std::env::vars()) {
+        check(Err(config_error));
+    }
+    if let Some(preset) = cli_args.preset() {
+        check(builder.select_preset(preset));
+    }
+
+    builder.build().unwrap_or_else(|config_error| {
+        if cli_args.strict() {
+            eprintln!("{}", config_error);
+            std::process::exit(1);
         }
-        println!();
+        eprintln!("{}. Using default configuration", config_error);
+        Config::default()
+    })
+}
+
+/// Load the accumulation buffer saved by an interrupted render, or start a new one
+fn resume_buffer(
+    renderer: &Renderer,
+    config: &Config,
+    accumulation_path: Option<&str>,
+) -> AccumulationBuffer {
+    let accumulation_path = match accumulation_path {
+        Some(path) if std::path::Path::new(path).exists() => path,
+        _ => return renderer.create_buffer(),
+    };
+
+    let buffer = AccumulationBuffer::load(accumulation_path).unwrap_or_else(|io_error| {
+        eprintln!("Unable to load '{}': {}", accumulation_path, io_error);
+        std::process::exit(1);
+    });
+
+    let image_config = config.image_config();
+    if buffer.width() != image_config.image_width() as usize
+        || buffer.height() != image_config.image_height() as usize
+    {
+        eprintln!(
+            "'{}' holds a {}x{} image, but the configured image is {}x{}",
+            accumulation_path,
+            buffer.width(),
+            buffer.height(),
+            image_config.image_width(),
+            image_config.image_height()
+        );
+        std::process::exit(1);
     }
+
+    eprintln!(
+        "Resuming from {} at {} samples per pixel",
+        accumulation_path,
+        buffer.target_samples()
+    );
+    buffer
+}
+
+fn write_output(framebuffer: &Framebuffer, path: &str, format: &ImageFormat, gamma: f64) {
+    write_image(framebuffer, path, format, gamma).unwrap_or_else(|io_error| {
+        eprintln!("Unable to write image '{}': {}", path, io_error);
+        std::process::exit(1);
+    });
 }
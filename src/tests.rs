@@ -1,2 +1,21 @@
+pub mod accumulation_buffer_t;
+pub mod bvh_t;
+pub mod camera_t;
+pub mod cli_t;
+pub mod config_builder_t;
+pub mod config_t;
+pub mod hdr_loader_t;
+pub mod image_writer_t;
+pub mod integrator_t;
+pub mod obj_loader_t;
+pub mod perlin_t;
+pub mod ray_t;
+pub mod render_stats_t;
+pub mod renderer_t;
+pub mod sampler_t;
+pub mod scene_t;
+pub mod texture_t;
+#[allow(clippy::needless_borrow)]
 pub mod traceable_t;
+pub mod triangle_t;
 pub mod vec3_t;
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::types::{
+        accumulation_buffer::{AccumulationBuffer, PixelAccumulator},
+        color::Color,
+    };
+
+    #[test]
+    fn test_pixel_accumulator() {
+        let mut pixel = PixelAccumulator::new();
+        assert_eq!(pixel.mean(), Color::zero_vec());
+        assert_eq!(pixel.standard_error(), f64::INFINITY);
+
+        pixel.add_sample(&Color::new(1.0, 1.0, 1.0));
+        pixel.add_sample(&Color::new(0.0, 0.0, 0.0));
+        assert_eq!(pixel.samples(), 2);
+        assert_eq!(pixel.mean(), Color::new(0.5, 0.5, 0.5));
+        assert!((pixel.luminance_mean() - 0.5).abs() < 1e-12);
+        assert!((pixel.standard_error() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_accumulation_buffer_round_trip() {
+        let mut buffer = AccumulationBuffer::new(3, 2, 42);
+        buffer.row_mut(1)[2].add_sample(&Color::new(0.25, 0.5, 0.75));
+        buffer.row_mut(0)[0].add_sample(&Color::new(2.0, 0.0, 1.0));
+        buffer.finish_pass(4);
+
+        let mut bytes = vec![];
+        buffer.write_to(&mut bytes).unwrap();
+        let loaded = AccumulationBuffer::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(loaded, buffer);
+        assert_eq!(loaded.seed(), 42);
+        assert_eq!(loaded.passes(), 1);
+        assert_eq!(loaded.target_samples(), 4);
+        assert_eq!(*loaded.to_framebuffer().pixel(2, 1), Color::new(0.25, 0.5, 0.75));
+        assert_eq!(*loaded.sample_counts(4).pixel(0, 0), Color::new(0.25, 0.25, 0.25));
+    }
+
+    #[test]
+    fn test_accumulation_buffer_rejects_invalid_files() {
+        assert!(AccumulationBuffer::read_from(&b"NOTACC01"[..]).is_err());
+
+        let mut bytes = vec![];
+        AccumulationBuffer::new(2, 2, 0)
+            .write_to(&mut bytes)
+            .unwrap();
+        bytes.truncate(bytes.len() - 1);
+        assert!(AccumulationBuffer::read_from(bytes.as_slice()).is_err());
+
+        // Sizes that overflow or exceed the pixel data are rejected before allocating
+        for (width, height) in [(u64::MAX, 2), (1 << 40, 1 << 20)] {
+            let mut bytes = b"RTACC001".to_vec();
+            for value in [width, height, 0, 0, 0] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            assert!(AccumulationBuffer::read_from(bytes.as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_accumulation_buffer_save_replaces_file() {
+        let path = std::env::temp_dir().join(format!("accumulation_t_{}.acc", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut buffer = AccumulationBuffer::new(2, 1, 7);
+        buffer.save(path).unwrap();
+        buffer.finish_pass(8);
+        buffer.save(path).unwrap();
+
+        assert_eq!(AccumulationBuffer::load(path).unwrap(), buffer);
+        assert!(!std::path::Path::new(&format!("{}.tmp", path)).exists());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{
+            bvh::BvhNode,
+            ray::Ray,
+            sphere::Sphere,
+            traceable::{Traceable, TraceableGroup},
+        },
+        types::{aabb::Aabb, color::Color, diffuse_mat::DiffuseMaterial, vec3::Vec3},
+        utils::utilities::MAX_F64,
+    };
+
+    fn sphere_grid() -> TraceableGroup {
+        let mut group = TraceableGroup { objects: vec![] };
+        for x in -4i32..4 {
+            for y in -3..3 {
+                for z in 1..4 {
+                    let center = Vec3::new(x as f64, y as f64 * 1.3, -(z as f64) * 2.0);
+                    let radius = 0.2 + 0.05 * ((x + y + z).rem_euclid(5) as f64);
+                    let material = Box::new(DiffuseMaterial::new(&Color::new(0.5, 0.5, 0.5)));
+                    group.add(Box::new(Sphere::new(&center, radius, material).unwrap()));
+                }
+            }
+        }
+        group
+    }
+
+    #[test]
+    fn test_aabb_intersects_ray() {
+        let aabb = Aabb::new(&Vec3::new(-1.0, -1.0, -3.0), &Vec3::new(1.0, 1.0, -2.0));
+
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 0.0, -1.0));
+        assert!(aabb.intersects_ray(&ray, 0.0, MAX_F64));
+        assert!(!aabb.intersects_ray(&ray, 0.0, 1.0));
+
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 1.0, 0.0));
+        assert!(!aabb.intersects_ray(&ray, 0.0, MAX_F64));
+
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(1.0, 1.0, -2.5));
+        assert!(aabb.intersects_ray(&ray, 0.0, MAX_F64));
+    }
+
+    #[test]
+    fn test_group_bounding_box() {
+        let group = sphere_grid();
+        let bounding_box = group.bounding_box().unwrap();
+        assert!(bounding_box.minimum().x() <= -4.2);
+        assert!(bounding_box.maximum().z() >= -2.4);
+
+        let empty = TraceableGroup { objects: vec![] };
+        assert!(empty.bounding_box().is_none());
+        assert!(BvhNode::from_group(empty).is_err());
+    }
+
+    #[test]
+    fn test_bvh_matches_linear_traversal() {
+        let linear = sphere_grid();
+        let bvh = BvhNode::from_group(sphere_grid()).unwrap();
+
+        for i in 0..40 {
+            for j in 0..30 {
+                let direction = Vec3::new(i as f64 / 5.0 - 4.0, j as f64 / 5.0 - 3.0, -1.0);
+                let ray = Ray::new(&Vec3::new(0.0, 0.0, 2.0), &direction);
+
+                let linear_hit = linear.intersects_ray(&ray, 0.00001, MAX_F64);
+                let bvh_hit = bvh.intersects_ray(&ray, 0.00001, MAX_F64);
+                assert_eq!(linear_hit.is_some(), bvh_hit.is_some());
+                if let (Some(linear_hit), Some(bvh_hit)) = (linear_hit, bvh_hit) {
+                    assert_eq!(linear_hit.trace(), bvh_hit.trace());
+                    assert_eq!(linear_hit.point(), bvh_hit.point());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    const DOUBLE_TEST_EPSILON: f64 = 0.00001;
+    use crate::{
+        components::camera::Camera, types::vec3::Vec3, utils::utilities::seeded_generator,
+    };
+
+    #[test]
+    fn test_center_ray_points_at_target() {
+        let mut rng = seeded_generator(0);
+        let look_from = Vec3::new(1.0, 2.0, 3.0);
+        let look_at = Vec3::new(-1.0, 0.0, -2.0);
+        let camera =
+            Camera::new(&look_from, &look_at, &Vec3::new(0.0, 1.0, 0.0), 60.0, 1.5, 0.0, 1.0);
+
+        let ray = camera.get_ray(0.5, 0.5, &mut rng);
+        assert_eq!(ray.origin(), &look_from);
+
+        let expected = (look_at - look_from).unit_vector().unwrap();
+        let actual = ray.direction().unit_vector().unwrap();
+        assert!((actual - expected).len() <= DOUBLE_TEST_EPSILON);
+    }
+
+    #[test]
+    fn test_field_of_view() {
+        let mut rng = seeded_generator(0);
+        let camera = Camera::new(
+            &Vec3::zero_vec(),
+            &Vec3::new(0.0, 0.0, -1.0),
+            &Vec3::new(0.0, 1.0, 0.0),
+            90.0,
+            2.0,
+            0.0,
+            1.0,
+        );
+
+        // Top edge of a 90 degree vertical field of view is 45 degrees above the view axis
+        let top = camera
+            .get_ray(0.5, 1.0, &mut rng)
+            .direction()
+            .unit_vector()
+            .unwrap();
+        assert!((top.y() - (0.5_f64).sqrt()).abs() <= DOUBLE_TEST_EPSILON);
+
+        // Horizontal extent is scaled by the aspect ratio
+        let right = camera.get_ray(1.0, 0.5, &mut rng);
+        assert!(
+            (right.direction().x() / -right.direction().z() - 2.0).abs() <= DOUBLE_TEST_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_defocus_converges_at_focus_distance() {
+        let mut rng = seeded_generator(0);
+        let look_from = Vec3::new(0.0, 0.0, 0.0);
+        let look_at = Vec3::new(0.0, 0.0, -3.0);
+        let camera =
+            Camera::new(&look_from, &look_at, &Vec3::new(0.0, 1.0, 0.0), 40.0, 1.0, 0.5, 3.0);
+
+        // Rays through the same viewport point leave from different points on the lens
+        // but all pass through the same point on the focus plane
+        let focus_point = Vec3::new(0.0, 0.0, -3.0);
+        let mut origins_differ = false;
+        for _ in 0..20 {
+            let ray = camera.get_ray(0.5, 0.5, &mut rng);
+            assert!((ray.origin().z()).abs() <= DOUBLE_TEST_EPSILON);
+            assert!(ray.origin().len() <= 0.5);
+            assert!((ray.at(1.0) - focus_point).len() <= DOUBLE_TEST_EPSILON);
+            origins_differ |= !ray.origin().is_nearly_zero();
+        }
+        assert!(origins_differ);
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        cli::{CliArgs, CliError},
+        config::{AntialiasingMode, Config, ImageFormat},
+    };
+
+    fn parse(arguments: &[&str]) -> Result<CliArgs, CliError> {
+        CliArgs::parse(arguments.iter().map(|argument| argument.to_string()))
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        let cli_args = parse(&[]).unwrap();
+        assert_eq!(cli_args.config_path(), "settings.toml");
+        assert_eq!(cli_args.scene_path(), "scene.toml");
+        assert_eq!(cli_args.output_path(), None);
+        assert_eq!(cli_args.samples(), None);
+        assert!(!cli_args.help());
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let cli_args = parse(&[
+            "-c",
+            "farm.toml",
+            "--scene=scenes/room.toml",
+            "-o",
+            "room.ppm",
+            "--width",
+            "640",
+            "--height=480",
+            "--samples",
+            "128",
+            "--seed",
+            "7",
+            "--threads",
+            "4",
+        ])
+        .unwrap();
+
+        assert_eq!(cli_args.config_path(), "farm.toml");
+        assert_eq!(cli_args.scene_path(), "scenes/room.toml");
+        assert_eq!(cli_args.output_path(), Some("room.ppm"));
+        assert_eq!(cli_args.width(), Some(640));
+        assert_eq!(cli_args.height(), Some(480));
+        assert_eq!(cli_args.samples(), Some(128));
+        assert_eq!(cli_args.seed(), Some(7));
+        assert_eq!(cli_args.threads(), Some(4));
+
+        assert_eq!(parse(&["-p", "draft"]).unwrap().preset(), Some("draft"));
+        assert!(parse(&["--help"]).unwrap().help());
+        assert!(parse(&["-h"]).unwrap().help());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse(&["--bogus"]),
+            Err(CliError::UnknownArgument {
+                argument: "--bogus".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["--width"]),
+            Err(CliError::MissingValue {
+                option: "--width".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["--samples", "many"]),
+            Err(CliError::InvalidValue {
+                option: "--samples".to_string(),
+                value: "many".to_string()
+            })
+        );
+        assert!(parse(&["--height=0"]).is_err());
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let mut config = Config::from_toml_str(
+            r#"
+            [image]
+            image_width = 100
+            image_height = 50
+            aa = { mode = "Adaptive", min_samples = 32, max_samples = 256 }
+            output_path = "render.png"
+            output_format = "PNG"
+
+            [camera]
+
+            [rays]
+
+            [render]
+            seed = 1
+            "#,
+        );
+
+        parse(&[
+            "--width",
+            "320",
+            "--samples",
+            "16",
+            "--seed",
+            "9",
+            "-o",
+            "out.ppm",
+        ])
+        .unwrap()
+        .apply(&mut config);
+
+        let image_config = config.image_config();
+        assert_eq!(image_config.image_width(), 320);
+        assert_eq!(image_config.image_height(), 50);
+        assert_eq!(image_config.samples_per_pixel(), 16);
+        assert_eq!(image_config.min_samples_per_pixel(), 16);
+        assert_eq!(image_config.output_path(), "out.ppm");
+        assert_eq!(image_config.output_format(), &ImageFormat::PPM);
+        assert_eq!(config.render_config().seed(), Some(9));
+
+        // Without antialiasing, a sample count switches to MSAA
+        let mut config = Config::default();
+        parse(&["--samples", "4"]).unwrap().apply(&mut config);
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::MSAA { samples: Some(4) });
+    }
+}
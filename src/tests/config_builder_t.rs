@@ -0,0 +1,285 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        types::vec3::Vec3,
+        utils::{
+            config::{AntialiasingMode, Config, ConfigError, SamplerMode},
+            config_builder::ConfigBuilder,
+            scene::SceneDescription,
+        },
+    };
+
+    fn env(variables: &[(&str, &str)]) -> Vec<(String, String)> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_defaults_layer() {
+        let config = ConfigBuilder::new().build().unwrap();
+        assert_eq!(config.image_config().image_width(), 256);
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::None {});
+        assert_eq!(*config.camera_config().vup(), Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_toml_str(
+                r#"
+                [image]
+                image_width = 800
+                image_height = 600
+                aa = { mode = "MSAA", samples = 64 }
+
+                [camera]
+                vertical_fov = 40.0
+                "#,
+            )
+            .unwrap();
+        builder
+            .add_toml_str(
+                r#"
+                [image.aa]
+                samples = 256
+
+                [camera]
+                look_from = [0.0, 1.0, 3.0]
+                "#,
+            )
+            .unwrap();
+        assert!(builder
+            .add_env(env(&[
+                ("RAYTRACER_IMAGE_WIDTH", "1920"),
+                ("RAYTRACER_RENDER_SEED", "5"),
+                ("RAYTRACER_IMAGE_OUTPUT_PATH", "shot.ppm"),
+                ("RAYTRACER_RENDER_PROGRESSIVE__PASS_SAMPLES", "4"),
+                ("HOME", "/root"),
+            ]))
+            .is_empty());
+        let config = builder.build().unwrap();
+
+        let image_config = config.image_config();
+        assert_eq!(image_config.image_width(), 1920);
+        assert_eq!(image_config.image_height(), 600);
+        assert_eq!(image_config.samples_per_pixel(), 256);
+        assert_eq!(image_config.output_path(), "shot.ppm");
+        assert_eq!(config.camera_config().vertical_fov(), 40.0);
+        assert_eq!(*config.camera_config().look_from(), Vec3::new(0.0, 1.0, 3.0));
+        assert_eq!(config.render_config().seed(), Some(5));
+        assert_eq!(config.render_config().progressive().unwrap().pass_samples(), 4);
+    }
+
+    #[test]
+    fn test_variant_change_replaces_options() {
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_toml_str(r#"image = { aa = { mode = "MSAA", samples = 64 } }"#)
+            .unwrap();
+        builder
+            .add_toml_str(r#"image = { aa = { mode = "Grid", size = 3 } }"#)
+            .unwrap();
+
+        assert_eq!(
+            builder.options()["image"]["aa"],
+            toml::Value::try_from(AntialiasingMode::Grid { size: 3 }).unwrap()
+        );
+        assert_eq!(builder.build().unwrap().image_config().samples_per_pixel(), 9);
+    }
+
+    #[test]
+    fn test_renamed_options() {
+        let mut builder = ConfigBuilder::new();
+        builder.add_toml_str("[image]\naa_mode = \"MSAA\"").unwrap();
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::MSAA { samples: None });
+
+        assert!(builder
+            .add_env(env(&[("RAYTRACER_IMAGE_AA_MODE", "None")]))
+            .is_empty());
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::None {});
+    }
+
+    #[test]
+    fn test_layer_errors() {
+        let mut builder = ConfigBuilder::new();
+        assert!(matches!(builder.add_file("missing_settings.toml"), Err(ConfigError::Io { .. })));
+        assert!(matches!(
+            builder.add_toml_str("[image"),
+            Err(ConfigError::Parse {
+                position: Some(_),
+                ..
+            })
+        ));
+
+        let errors = builder.add_env(env(&[("RAYTRACER_IMAGE_HEIGHT", "tall")]));
+        let message = errors[0].to_string();
+        assert!(message.contains("RAYTRACER_IMAGE_HEIGHT"), "{}", message);
+        assert!(message.contains("image.image_height"), "{}", message);
+    }
+
+    #[test]
+    fn test_env_errors_leave_out_their_variables() {
+        let mut builder = ConfigBuilder::new();
+        let errors = builder.add_env(env(&[
+            ("RAYTRACER_FOO", "1"),
+            ("RAYTRACER_WIDTH", "800"),
+            ("RAYTRACER_IMAGE_WIDHT", "800"),
+            ("RAYTRACER_IMAGE_HEIGHT", "600"),
+            ("RAYTRACER_IMAGE_AA__MODE", "Grid"),
+            ("RAYTRACER_IMAGE_AA__SIZE", "3"),
+        ]));
+
+        let options: Vec<String> = errors
+            .into_iter()
+            .map(|config_error| match config_error {
+                ConfigError::Validation { option, .. } => option,
+                config_error => panic!("Unexpected error {:?}", config_error),
+            })
+            .collect();
+        assert_eq!(options, ["RAYTRACER_FOO", "RAYTRACER_WIDTH", "RAYTRACER_IMAGE_WIDHT"]);
+
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().image_height(), 600);
+        assert_eq!(config.image_config().samples_per_pixel(), 9);
+    }
+
+    #[test]
+    fn test_invalid_layers_are_left_out() {
+        let config_file = std::env::temp_dir()
+            .join(format!("config_builder_t_invalid_{}.toml", std::process::id()));
+        let config_path = config_file.to_str().unwrap();
+        std::fs::write(&config_file, "[image]\nimage_width = 800\nimage_height = \"tall\"\n")
+            .unwrap();
+
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_toml_str("[camera]\nvertical_fov = 40.0")
+            .unwrap();
+        let add_result = builder.add_file(config_path);
+        std::fs::remove_file(&config_file).unwrap();
+
+        match add_result {
+            Err(ConfigError::Parse { path, position, .. }) => {
+                assert_eq!(path.as_deref(), Some(config_path));
+                assert_eq!(position, Some((2, 15)));
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert!(matches!(
+            builder.add_toml_str("[image]\nimage_width = 0"),
+            Err(ConfigError::Validation { .. })
+        ));
+
+        // The layers before the invalid ones are kept
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().image_width(), 256);
+        assert_eq!(config.camera_config().vertical_fov(), 40.0);
+    }
+
+    #[test]
+    fn test_dump_round_trips() {
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_toml_str(
+                r#"
+                [image]
+                aa = { mode = "Adaptive", max_samples = 128 }
+                sampler = "Halton"
+
+                [background]
+                type = "Solid"
+                color = [0.1, 0.2, 0.3]
+                "#,
+            )
+            .unwrap();
+        let dump = builder.build().unwrap().to_toml_string();
+
+        let reloaded = Config::parse(&dump).unwrap();
+        assert_eq!(reloaded.to_toml_string(), dump);
+        assert_eq!(reloaded.image_config().samples_per_pixel(), 128);
+    }
+
+    #[test]
+    fn test_scene_settings_layer() {
+        let scene_file =
+            std::env::temp_dir().join(format!("config_builder_t_{}.toml", std::process::id()));
+        let scene_toml = r#"
+            [settings.camera]
+            vertical_fov = 30.0
+
+            [materials.matte]
+            type = "Diffuse"
+            albedo = [0.5, 0.5, 0.5]
+        "#;
+        std::fs::write(&scene_file, scene_toml).unwrap();
+
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_scene_settings(scene_file.to_str().unwrap())
+            .unwrap();
+        std::fs::remove_file(&scene_file).unwrap();
+
+        assert_eq!(builder.build().unwrap().camera_config().vertical_fov(), 30.0);
+        assert!(SceneDescription::from_toml_str(scene_toml).is_ok());
+
+        // Scenes without settings, or missing scenes, leave the options unchanged
+        builder.add_scene_settings("missing_scene.toml").unwrap();
+        assert_eq!(builder.build().unwrap().camera_config().vertical_fov(), 30.0);
+    }
+
+    #[test]
+    fn test_preset_layer() {
+        let mut builder = ConfigBuilder::new();
+        builder
+            .add_toml_str("preset = \"final\"\n[image]\nimage_width = 400\nimage_height = 300")
+            .unwrap();
+        assert_eq!(builder.build().unwrap().image_config().samples_per_pixel(), 512);
+
+        // Later preset selections replace earlier ones
+        assert!(builder
+            .add_env(env(&[("RAYTRACER_PRESET", "preview")]))
+            .is_empty());
+        assert_eq!(builder.build().unwrap().image_config().image_width(), 200);
+        builder.select_preset("draft").unwrap();
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().image_width(), 100);
+        assert_eq!(config.image_config().samples_per_pixel(), 4);
+        assert_eq!(config.rays_config().max_child_rays(), 8);
+
+        // A selected preset overrides the config layers, and environment variables
+        // override the preset
+        assert!(builder
+            .add_env(env(&[("RAYTRACER_IMAGE_AA__SAMPLES", "999")]))
+            .is_empty());
+        builder
+            .add_toml_str("[rays]\nmax_child_rays = 100")
+            .unwrap();
+        let config = builder.build().unwrap();
+        assert_eq!(config.image_config().samples_per_pixel(), 999);
+        assert_eq!(config.rays_config().max_child_rays(), 8);
+
+        assert!(matches!(
+            builder.select_preset("cinematic"),
+            Err(ConfigError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_preset_overrides_shipped_settings() {
+        let mut builder = ConfigBuilder::new();
+        builder.add_file("settings.toml").unwrap();
+        let settings = builder.build().unwrap();
+        builder.select_preset("draft").unwrap();
+        let config = builder.build().unwrap();
+
+        assert_eq!(config.image_config().image_width(), settings.image_config().image_width() / 4);
+        assert_eq!(config.image_config().samples_per_pixel(), 4);
+        assert_eq!(config.rays_config().max_child_rays(), 8);
+        assert_eq!(config.image_config().sampler(), &SamplerMode::Independent);
+    }
+}
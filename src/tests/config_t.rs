@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        types::vec3::Vec3,
+        utils::config::{AntialiasingMode, Config, ConfigError, SamplerMode},
+    };
+
+    fn config_with_image(image_options: &str) -> Config {
+        Config::from_toml_str(&format!(
+            r#"
+            [image]
+            image_width = 16
+            image_height = 12
+            {}
+
+            [camera]
+
+            [rays]
+            "#,
+            image_options
+        ))
+    }
+
+    #[test]
+    fn test_antialiasing_modes() {
+        let config = config_with_image(r#"aa = { mode = "MSAA", samples = 200 }"#);
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::MSAA { samples: Some(200) });
+        assert_eq!(config.image_config().samples_per_pixel(), 200);
+
+        let config = config_with_image(r#"aa = { mode = "MSAA" }"#);
+        assert_eq!(config.image_config().samples_per_pixel(), 50);
+
+        let config = config_with_image(
+            r#"
+            [image.aa]
+            mode = "Grid"
+            size = 3
+            "#,
+        );
+        assert_eq!(config.image_config().samples_per_pixel(), 9);
+
+        let config = config_with_image(
+            r#"aa = { mode = "Adaptive", min_samples = 8, max_samples = 64, threshold = 0.05 }"#,
+        );
+        assert_eq!(config.image_config().samples_per_pixel(), 64);
+        assert_eq!(config.image_config().min_samples_per_pixel(), 8);
+        assert_eq!(config.image_config().noise_threshold(), Some(0.05));
+        assert_eq!(config.image_config().sample_count_path(), None);
+
+        let config =
+            config_with_image(r#"aa = { mode = "Adaptive", sample_count_path = "samples.png" }"#);
+        assert_eq!(config.image_config().samples_per_pixel(), 256);
+        assert_eq!(config.image_config().min_samples_per_pixel(), 16);
+        assert_eq!(config.image_config().sample_count_path(), Some("samples.png"));
+
+        let config = config_with_image(r#"aa = { mode = "None" }"#);
+        assert_eq!(config.image_config().samples_per_pixel(), 1);
+
+        let config = config_with_image("");
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::None {});
+        assert_eq!(config.image_config().samples_per_pixel(), 1);
+
+        // Mode names of the former aa_mode option
+        let config = config_with_image(r#"aa_mode = "MSAA""#);
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::MSAA { samples: None });
+        let config = config_with_image(r#"aa_mode = "None""#);
+        assert_eq!(config.image_config().aa_mode(), &AntialiasingMode::None {});
+    }
+
+    #[test]
+    fn test_vectors_as_arrays() {
+        let config = Config::parse(
+            r#"
+            [image]
+            image_width = 16
+            image_height = 12
+
+            [camera]
+            look_from = [1.0, 2.0, 3.0]
+            look_at = [0.0, 0.0, -1.0]
+
+            [rays]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(*config.camera_config().look_from(), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(*config.camera_config().look_at(), Vec3::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_load_errors() {
+        match Config::load("missing_settings.toml") {
+            Err(ConfigError::Io { path, .. }) => assert_eq!(path, "missing_settings.toml"),
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        let parse_error = Config::parse("[image]\nimage_width = 16\nimage_height = \"tall\"\n");
+        match parse_error {
+            Err(ConfigError::Parse { position, .. }) => assert_eq!(position, Some((2, 15))),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert!(parse_error
+            .unwrap_err()
+            .to_string()
+            .starts_with("Unable to parse config at line 3, column 16"));
+    }
+
+    #[test]
+    fn test_unknown_options() {
+        for (toml_content, option) in [
+            ("[image]\nimage_widht = 16\n", "image_widht"),
+            ("[image]\naa = { mode = \"MSAA\", sample = 4 }\n", "sample"),
+            ("[image]\naa = { mode = \"None\", samples = 4 }\n", "samples"),
+            ("[background]\ntype = \"Black\"\ncolor = [1.0, 1.0, 1.0]\n", "color"),
+            ("[render.progressive]\npass_sample = 4\n", "pass_sample"),
+            ("[presets.fast]\nsample = 4\n", "sample"),
+            ("[renderer]\nthreads = 4\n", "renderer"),
+        ] {
+            match Config::parse(toml_content) {
+                Err(ConfigError::Parse { message, .. }) => {
+                    assert!(message.contains(&format!("unknown field `{}`", option)))
+                }
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        let validate = |image_options: &str, camera_options: &str| {
+            Config::parse(&format!(
+                r#"
+                [image]
+                {}
+
+                [camera]
+                {}
+
+                [rays]
+                "#,
+                image_options, camera_options
+            ))
+        };
+
+        assert!(validate("image_width = 16\nimage_height = 12", "").is_ok());
+
+        for (image_options, camera_options, option) in [
+            ("image_width = 0\nimage_height = 12", "", "image.image_width"),
+            ("image_width = 16\nimage_height = 0", "", "image.image_height"),
+            ("image_width = 16\nimage_height = 12\ngamma = 0.0", "", "image.gamma"),
+            (
+                "image_width = 16\nimage_height = 12\naa = { mode = \"Grid\", size = 0 }",
+                "",
+                "image.aa.size",
+            ),
+            (
+                "image_width = 16\nimage_height = 12",
+                "vertical_fov = 180.0",
+                "camera.vertical_fov",
+            ),
+            ("image_width = 16\nimage_height = 12", "aperture = -1.0", "camera.aperture"),
+            (
+                "image_width = 16\nimage_height = 12",
+                "look_at = [0.0, 0.0, 0.0]",
+                "camera.look_at",
+            ),
+            ("image_width = 16\nimage_height = 12", "vup = [0.0, 0.0, 2.0]", "camera.vup"),
+        ] {
+            match validate(image_options, camera_options) {
+                Err(ConfigError::Validation {
+                    option: invalid, ..
+                }) => assert_eq!(invalid, option),
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+
+        // Invalid configs fall back to the defaults when loaded leniently
+        let config =
+            Config::from_toml_str("[image]\nimage_width = 0\nimage_height = 12\n[camera]\n[rays]");
+        assert_eq!(config.image_config().image_width(), 256);
+    }
+
+    #[test]
+    fn test_builtin_presets() {
+        let config = Config::parse(
+            r#"
+            preset = "draft"
+
+            [image]
+            image_width = 800
+            image_height = 600
+            "#,
+        )
+        .unwrap();
+
+        let image_config = config.image_config();
+        assert_eq!(image_config.image_width(), 200);
+        assert_eq!(image_config.image_height(), 150);
+        assert_eq!(image_config.samples_per_pixel(), 4);
+        assert_eq!(image_config.sampler(), &SamplerMode::Independent);
+        assert_eq!(config.rays_config().max_child_rays(), 8);
+
+        // The resolved config no longer selects the preset, so reloading it is stable
+        let dump = config.to_toml_string();
+        assert!(!dump.contains("preset"));
+        assert_eq!(Config::parse(&dump).unwrap().image_config().image_width(), 200);
+
+        // Options set in the config override those of the preset
+        let config = Config::parse(
+            r#"
+            preset = "draft"
+
+            [image]
+            image_width = 800
+            image_height = 600
+            aa = { mode = "MSAA", samples = 500 }
+            sampler = "Sobol"
+
+            [rays]
+            max_child_rays = 100
+            "#,
+        )
+        .unwrap();
+
+        let image_config = config.image_config();
+        assert_eq!(image_config.image_width(), 200);
+        assert_eq!(image_config.samples_per_pixel(), 500);
+        assert_eq!(image_config.sampler(), &SamplerMode::Sobol);
+        assert_eq!(config.rays_config().max_child_rays(), 100);
+    }
+
+    #[test]
+    fn test_user_defined_presets() {
+        let config = Config::parse(
+            r#"
+            preset = "final"
+
+            [image]
+            image_width = 100
+            image_height = 50
+
+            [presets.final]
+            resolution_scale = 2.0
+            samples = 2048
+            "#,
+        )
+        .unwrap();
+
+        // User presets replace built-in presets and leave unset options unchanged
+        let image_config = config.image_config();
+        assert_eq!(image_config.image_width(), 200);
+        assert_eq!(image_config.image_height(), 100);
+        assert_eq!(image_config.samples_per_pixel(), 2048);
+        assert_eq!(image_config.sampler(), &SamplerMode::Independent);
+        assert_eq!(config.rays_config().max_child_rays(), 50);
+
+        match Config::parse(r#"preset = "cinematic""#) {
+            Err(ConfigError::Validation { option, .. }) => assert_eq!(option, "preset"),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+}
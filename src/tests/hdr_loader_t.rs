@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        types::color::Color,
+        utils::hdr_loader::{load_hdr, parse_hdr, HdrError},
+    };
+
+    fn hdr_header(width: usize, height: usize) -> Vec<u8> {
+        format!("#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n", height, width).into_bytes()
+    }
+
+    #[test]
+    fn test_parse_flat_hdr() {
+        let mut content = hdr_header(2, 1);
+        content.extend_from_slice(&[128, 64, 32, 129, 0, 0, 0, 0]);
+
+        let image = parse_hdr(&content).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(*image.pixel(0, 0), Color::new(1.0, 0.5, 0.25));
+        assert_eq!(*image.pixel(1, 0), Color::zero_vec());
+    }
+
+    #[test]
+    fn test_parse_rle_hdr() {
+        let mut content = hdr_header(8, 1);
+        content.extend_from_slice(&[2, 2, 0, 8]);
+        // Red, green and exponent as runs, blue as literal values
+        content.extend_from_slice(&[128 + 8, 128]);
+        content.extend_from_slice(&[128 + 8, 64]);
+        content.push(8);
+        content.extend_from_slice(&[32; 8]);
+        content.extend_from_slice(&[128 + 8, 129]);
+
+        let image = parse_hdr(&content).unwrap();
+        assert_eq!((image.width(), image.height()), (8, 1));
+        assert!(image
+            .pixels()
+            .iter()
+            .all(|pixel| *pixel == Color::new(1.0, 0.5, 0.25)));
+    }
+
+    #[test]
+    fn test_invalid_hdr() {
+        assert!(parse_hdr(b"P6\n1 1\n255\n").is_err());
+
+        let mut truncated = hdr_header(2, 2);
+        truncated.extend_from_slice(&[128, 64, 32, 129]);
+        assert!(parse_hdr(&truncated).is_err());
+
+        let xyze = b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n\0\0\0\0";
+        assert!(parse_hdr(xyze).is_err());
+
+        for (width, height) in [(0, 2), (2, 0)] {
+            assert!(parse_hdr(&hdr_header(width, height)).is_err());
+        }
+
+        // Sizes that the pixel data cannot hold are rejected before allocating them
+        for (width, height) in [
+            (4_000_000_000, 4_000_000_000),
+            (usize::MAX, 1),
+            (1000, 1000),
+        ] {
+            let mut oversized = hdr_header(width, height);
+            oversized.extend_from_slice(&[2, 2, 3, 232]);
+            assert_eq!(parse_hdr(&oversized).unwrap_err(), "Image size exceeds the pixel data");
+        }
+
+        assert!(matches!(load_hdr("does/not/exist.hdr"), Err(HdrError::Io { .. })));
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        types::{color::Color, framebuffer::Framebuffer},
+        utils::image_writer::{write_png, write_ppm},
+    };
+
+    fn test_framebuffer() -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(3, 2);
+        framebuffer.set_pixel(0, 0, &Color::new(1.0, 0.0, 0.0));
+        framebuffer.set_pixel(2, 1, &Color::new(0.25, 0.5, 2.0));
+        framebuffer
+    }
+
+    #[test]
+    fn test_rgb8_conversion() {
+        let rgb = test_framebuffer().to_rgb8(1.0);
+        assert_eq!(rgb.len(), 3 * 2 * 3);
+        assert_eq!(&rgb[0..3], &[255, 0, 0]);
+        assert_eq!(&rgb[15..18], &[64, 128, 255]);
+
+        // Gamma 2 brightens mid tones
+        let rgb = test_framebuffer().to_rgb8(2.0);
+        assert_eq!(&rgb[15..18], &[128, 181, 255]);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut ppm_data: Vec<u8> = vec![];
+        write_ppm(&test_framebuffer(), &mut ppm_data, 1.0).unwrap();
+
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&ppm_data[..header.len()], header);
+        assert_eq!(ppm_data.len(), header.len() + 3 * 2 * 3);
+        assert_eq!(&ppm_data[header.len()..header.len() + 3], &[255, 0, 0]);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let mut png_data: Vec<u8> = vec![];
+        write_png(&test_framebuffer(), &mut png_data, 1.0).unwrap();
+
+        let decoder = png::Decoder::new(png_data.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut decoded).unwrap();
+
+        assert_eq!((frame.width, frame.height), (3, 2));
+        assert_eq!(frame.color_type, png::ColorType::Rgb);
+        assert_eq!(&decoded[..frame.buffer_size()], test_framebuffer().to_rgb8(1.0).as_slice());
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{
+            background::Background,
+            integrator::{
+                configure_integrator, Integrator, IterativeIntegrator, RecursiveIntegrator,
+            },
+            ray::Ray,
+        },
+        types::{color::Color, vec3::Vec3},
+        utils::{
+            config::{Config, IntegratorMode},
+            scene::SceneDescription,
+            utilities::{random_unit_vector, seeded_generator},
+        },
+    };
+
+    const SCENE: &str = r#"
+        [materials.matte]
+        type = "Diffuse"
+        albedo = [0.6, 0.5, 0.4]
+
+        [materials.mirror]
+        type = "Metal"
+        albedo = [0.9, 0.9, 0.9]
+        roughness = 0.2
+
+        [materials.glass]
+        type = "Dielectric"
+        ior = 1.5
+
+        [materials.lamp]
+        type = "DiffuseLight"
+        color = [4.0, 4.0, 4.0]
+
+        [[objects]]
+        type = "Sphere"
+        center = [0.0, -100.5, -1.0]
+        radius = 100.0
+        material = "matte"
+
+        [[objects]]
+        type = "Sphere"
+        center = [-1.0, 0.0, -1.0]
+        radius = 0.5
+        material = "mirror"
+
+        [[objects]]
+        type = "Sphere"
+        center = [1.0, 0.0, -1.0]
+        radius = 0.5
+        material = "glass"
+
+        [[objects]]
+        type = "Sphere"
+        center = [0.0, 2.0, -1.0]
+        radius = 0.5
+        material = "lamp"
+    "#;
+
+    #[test]
+    fn test_configure_integrator() {
+        let config = Config::parse("[render]\nintegrator = \"Iterative\"").unwrap();
+        assert_eq!(config.render_config().integrator(), &IntegratorMode::Iterative);
+        assert_eq!(Config::default().render_config().integrator(), &IntegratorMode::Recursive);
+        configure_integrator(&config);
+    }
+
+    #[test]
+    fn test_iterative_matches_recursive() {
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+        let background = Background::Solid(Color::new(0.2, 0.3, 0.5));
+
+        for rays_options in ["russian_roulette = false", "roulette_min_depth = 0"] {
+            let config = Config::parse(&format!("[rays]\n{}", rays_options)).unwrap();
+
+            let mut direction_rng = seeded_generator(17);
+            for seed in 0..2000 {
+                let ray = Ray::new(&Vec3::zero_vec(), &random_unit_vector(&mut direction_rng));
+
+                // The same random numbers give the same path through the scene
+                let recursive = RecursiveIntegrator {}.radiance(
+                    &ray,
+                    &scene_objects,
+                    &background,
+                    &config,
+                    &mut seeded_generator(seed),
+                );
+                let iterative = IterativeIntegrator {}.radiance(
+                    &ray,
+                    &scene_objects,
+                    &background,
+                    &config,
+                    &mut seeded_generator(seed),
+                );
+                assert!(
+                    (recursive - iterative).len() <= 1e-9 * recursive.len().max(1.0),
+                    "{:?} != {:?}",
+                    recursive,
+                    iterative
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        components::{ray::Ray, traceable::Traceable},
+        types::vec3::Vec3,
+        utils::{
+            obj_loader::{parse_mtl, ObjError, ObjModel},
+            utilities::MAX_F64,
+        },
+    };
+
+    const QUAD_OBJ: &str = "
+        # Unit quad facing +z, written as a single polygon
+        v -1.0 -1.0 -2.0
+        v 1.0 -1.0 -2.0
+        v 1.0 1.0 -2.0
+        v -1.0 1.0 -2.0
+        vt 0.0 0.0
+        vt 1.0 0.0
+        vt 1.0 1.0
+        vt 0.0 1.0
+        vn 0.0 0.0 1.0
+        f 1/1/1 2/2/1 3/3/1 4/4/1
+    ";
+
+    fn parse_error_line(result: Result<ObjModel, ObjError>) -> usize {
+        match result {
+            Err(ObjError::Parse { line, .. }) => line,
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_polygon_triangulation() {
+        let model = ObjModel::from_obj_str(QUAD_OBJ, "quad.obj").unwrap();
+        assert_eq!(model.triangle_count(), 2);
+
+        let meshes = model.build(None, &HashMap::new()).unwrap();
+        assert_eq!(meshes.objects.len(), 1);
+
+        let ray = Ray::new(&Vec3::new(0.5, 0.5, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = meshes.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert_eq!(hit_record.normal(), &Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_negative_indices() {
+        let obj = "
+            v 0.0 0.0 -1.0
+            v 1.0 0.0 -1.0
+            v 0.0 1.0 -1.0
+            f -3 -2 -1
+            v 0.0 0.0 -2.0
+            v 1.0 0.0 -2.0
+            v 0.0 1.0 -2.0
+            f -3//  -2 -1
+        ";
+        let model = ObjModel::from_obj_str(obj, "negative.obj").unwrap();
+        assert_eq!(model.triangle_count(), 2);
+
+        // The closest hit is the first triangle, even though the second one is also on the ray
+        let meshes = model.build(None, &HashMap::new()).unwrap();
+        let ray = Ray::new(&Vec3::new(0.2, 0.2, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = meshes.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert_eq!(hit_record.trace(), 1.0);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let obj = "v 0.0 0.0 0.0\nv 1.0 0.0 0.0\nv 0.0 one 0.0\n";
+        assert_eq!(parse_error_line(ObjModel::from_obj_str(obj, "bad.obj")), 3);
+
+        let obj = "v 0.0 0.0 0.0\nv 1.0 0.0 0.0\n\nf 1 2 3\n";
+        assert_eq!(parse_error_line(ObjModel::from_obj_str(obj, "bad.obj")), 4);
+
+        let obj = "v 0.0 0.0 0.0\nv 1.0 0.0 0.0\nv 0.0 1.0 0.0\nf 1 2\n";
+        assert_eq!(parse_error_line(ObjModel::from_obj_str(obj, "bad.obj")), 4);
+
+        let error = ObjModel::from_obj_str("v 0.0 0.0\n", "models/bad.obj").unwrap_err();
+        assert!(error.to_string().starts_with("models/bad.obj:1: "));
+    }
+
+    #[test]
+    fn test_unknown_usemtl() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl missing\nf 1 2 3\n";
+        let model = ObjModel::from_obj_str(obj, "model.obj").unwrap();
+        match model.build(None, &HashMap::new()) {
+            Err(ObjError::Parse { line, message, .. }) => {
+                assert_eq!(line, 4);
+                assert!(message.contains("missing"));
+            }
+            _ => panic!("Expected an unknown material error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_mtl() {
+        let mtl = "
+            newmtl red
+            Kd 0.8 0.1 0.1
+            Ks 0.2 0.2 0.2
+
+            newmtl chrome
+            Kd 0.1 0.1 0.1
+            Ks 0.9 0.9 0.9
+            Ns 500
+
+            newmtl glass
+            Ni 1.45
+            d 0.1
+
+            newmtl lamp
+            Ke 5.0 5.0 4.0
+        ";
+        let materials = parse_mtl(mtl, "materials.mtl").unwrap();
+        assert_eq!(materials.len(), 4);
+        assert!(materials.contains_key("red"));
+        assert!(materials.contains_key("chrome"));
+        assert!(materials.contains_key("glass"));
+
+        assert!(parse_mtl("Kd 1 1 1\n", "materials.mtl").is_err());
+        assert!(parse_mtl("newmtl red\nKd 1 1\n", "materials.mtl").is_err());
+    }
+
+    #[test]
+    fn test_load_with_material_library() {
+        let directory = std::env::temp_dir().join(format!("obj_loader_t_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("quad.mtl"), "newmtl white\nKd 1 1 1\n").unwrap();
+        std::fs::write(
+            directory.join("quad.obj"),
+            format!("mtllib quad.mtl\nusemtl white\n{}", QUAD_OBJ),
+        )
+        .unwrap();
+
+        let model = ObjModel::from_file(directory.join("quad.obj").to_str().unwrap()).unwrap();
+        assert_eq!(model.material_libraries(), &["quad.mtl".to_string()]);
+
+        let materials = model.load_materials().unwrap();
+        assert!(model.build(None, &materials).is_ok());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
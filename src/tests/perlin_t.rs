@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        types::{
+            color::{COLOR_BLACK, COLOR_WHITE},
+            noise_tex::{NoiseStyle, NoiseTexture},
+            texture::Texture,
+            vec3::Vec3,
+        },
+        utils::{perlin::Perlin, scene::SceneDescription},
+    };
+
+    fn sample_points() -> Vec<Vec3> {
+        (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.173;
+                Vec3::new(t.sin() * 7.3, t * 0.37 - 20.0, (2.0 * t).cos() * 3.1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_noise_is_deterministic() {
+        let noise = Perlin::new(42);
+        let same_seed = Perlin::new(42);
+        let other_seed = Perlin::new(43);
+
+        let points = sample_points();
+        assert!(points
+            .iter()
+            .all(|point| noise.noise(point) == same_seed.noise(point)));
+        assert!(points
+            .iter()
+            .any(|point| noise.noise(point) != other_seed.noise(point)));
+    }
+
+    #[test]
+    fn test_noise_range() {
+        let noise = Perlin::new(7);
+
+        // Gradient noise vanishes on the lattice
+        assert_eq!(noise.noise(&Vec3::new(3.0, -2.0, 5.0)), 0.0);
+
+        for point in sample_points() {
+            let value = noise.noise(&point);
+            assert!((-1.0..=1.0).contains(&value));
+            assert!(noise.turbulence(&point, 7) >= 0.0);
+        }
+        assert!(sample_points()
+            .iter()
+            .any(|point| noise.noise(point).abs() > 0.01));
+    }
+
+    #[test]
+    fn test_noise_texture() {
+        for style in [
+            NoiseStyle::Smooth,
+            NoiseStyle::Cloud,
+            NoiseStyle::Marble,
+            NoiseStyle::Wood,
+        ] {
+            let texture = NoiseTexture::new(style, 4.0, 7, 1, &COLOR_BLACK, &COLOR_WHITE);
+            for point in sample_points() {
+                let intensity = texture.intensity(&point);
+                assert!((0.0..=1.0).contains(&intensity));
+                assert_eq!(texture.value(0.0, 0.0, &point), COLOR_WHITE.scaled(intensity));
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_texture_from_scene() {
+        let scene = SceneDescription::from_toml_str(
+            r#"
+            [textures.marble]
+            type = "Noise"
+            style = "Marble"
+            scale = 4.0
+            seed = 12
+
+            [materials.statue]
+            type = "Diffuse"
+            albedo = "marble"
+
+            [[objects]]
+            type = "Sphere"
+            center = [0.0, 0.0, -1.0]
+            radius = 0.5
+            material = "statue"
+            "#,
+        )
+        .unwrap()
+        .build();
+        assert!(scene.is_ok());
+
+        let scene = SceneDescription::from_toml_str(
+            r#"
+            [textures.marble]
+            type = "Noise"
+            style = "Granite"
+            "#,
+        );
+        assert!(scene.is_err());
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{background::Background, ray::Ray, sphere::Sphere, traceable::TraceableGroup},
+        types::{
+            color::{Color, COLOR_BLACK, COLOR_WHITE},
+            diffuse_light::DiffuseLight,
+            diffuse_mat::DiffuseMaterial,
+            framebuffer::Framebuffer,
+            vec3::Vec3,
+        },
+        utils::{config::Config, utilities::seeded_generator},
+    };
+
+    #[test]
+    fn test_ray_color_of_light() {
+        let mut rng = seeded_generator(0);
+        let emit = Color::new(4.0, 2.0, 1.0);
+        let light =
+            Sphere::new(&Vec3::new(0.0, 0.0, -2.0), 0.5, Box::new(DiffuseLight::new(&emit)));
+        let scene_objects = TraceableGroup {
+            objects: vec![Box::new(light.unwrap())],
+        };
+
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(
+            ray.ray_color(
+                &scene_objects,
+                &Background::Solid(COLOR_BLACK),
+                &Config::default(),
+                &mut rng
+            ),
+            emit
+        );
+    }
+
+    #[test]
+    fn test_ray_color_of_miss() {
+        let mut rng = seeded_generator(0);
+        let scene_objects = TraceableGroup { objects: vec![] };
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 1.0, 0.0));
+
+        let solid = Background::Solid(Color::new(0.2, 0.3, 0.4));
+        assert_eq!(
+            ray.ray_color(&scene_objects, &solid, &Config::default(), &mut rng),
+            Color::new(0.2, 0.3, 0.4)
+        );
+
+        let gradient = Background::Gradient {
+            bottom: COLOR_WHITE,
+            top: Color::new(0.5, 0.7, 1.0),
+        };
+        assert_eq!(
+            ray.ray_color(&scene_objects, &gradient, &Config::default(), &mut rng),
+            Color::new(0.5, 0.7, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_background_from_config() {
+        let config = Config::from_toml_str(
+            r#"
+            [image]
+            image_width = 16
+            image_height = 12
+            gamma = 2.0
+            aa = { mode = "None" }
+
+            [camera]
+            look_from = [0.0, 0.0, 0.0]
+            look_at = [0.0, 0.0, -1.0]
+            vup = [0.0, 1.0, 0.0]
+            vertical_fov = 90.0
+            aperture = 0.0
+
+            [rays]
+            max_child_rays = 10
+            diffuse_scatter_mode = "Hemispherical"
+
+            [background]
+            type = "Black"
+            "#,
+        );
+
+        let background = Background::configure(&config).unwrap();
+        assert_eq!(background.color(&Vec3::new(0.0, 1.0, 0.0)), COLOR_BLACK);
+        assert_eq!(
+            Background::configure(&Config::default())
+                .unwrap()
+                .color(&Vec3::new(0.0, -1.0, 0.0)),
+            COLOR_WHITE
+        );
+    }
+
+    #[test]
+    fn test_environment_map_lookup() {
+        // Left half of the map is red, right half is blue
+        let mut image = Framebuffer::new(4, 2);
+        for y in 0..2 {
+            for x in 0..4 {
+                let color = if x < 2 {
+                    Color::new(1.0, 0.0, 0.0)
+                } else {
+                    Color::new(0.0, 0.0, 1.0)
+                };
+                image.set_pixel(x, y, &color);
+            }
+        }
+
+        let environment = Background::EnvironmentMap {
+            image: image.clone(),
+            rotation: 0.0,
+            intensity: 2.0,
+        };
+        assert_eq!(environment.color(&Vec3::new(-1.0, 0.0, 0.0)), Color::new(2.0, 0.0, 0.0));
+        assert_eq!(environment.color(&Vec3::new(1.0, 0.0, 0.0)), Color::new(0.0, 0.0, 2.0));
+
+        // Half a turn swaps the two sides
+        let rotated = Background::EnvironmentMap {
+            image,
+            rotation: std::f64::consts::PI,
+            intensity: 1.0,
+        };
+        let color = rotated.color(&Vec3::new(-1.0, 0.0, 0.0));
+        assert!((color - Color::new(0.0, 0.0, 1.0)).len() < 1e-9);
+    }
+
+    #[test]
+    fn test_russian_roulette_keeps_expected_color() {
+        let ground = Sphere::new(
+            &Vec3::new(0.0, -100.5, 0.0),
+            100.0,
+            Box::new(DiffuseMaterial::new(&Color::new(0.5, 0.5, 0.5))),
+        );
+        let scene_objects = TraceableGroup {
+            objects: vec![Box::new(ground.unwrap())],
+        };
+        let background = Background::Solid(COLOR_WHITE);
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, -1.0, 0.0));
+
+        let average_color = |rays_options: &str| {
+            let config = Config::parse(&format!("[rays]\n{}", rays_options)).unwrap();
+            let mut rng = seeded_generator(5);
+            let samples = 20000;
+            let mut total = Color::zero_vec();
+            for _ in 0..samples {
+                total += ray.ray_color(&scene_objects, &background, &config, &mut rng);
+            }
+            total.scaled(1.0 / samples as f64)
+        };
+
+        let without_roulette = average_color("russian_roulette = false");
+        let with_roulette = average_color("russian_roulette = true\nroulette_min_depth = 0");
+        assert!(
+            (without_roulette - with_roulette).len() < 0.03,
+            "{:?} != {:?}",
+            without_roulette,
+            with_roulette
+        );
+    }
+}
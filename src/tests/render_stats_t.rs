@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        components::{
+            background::Background,
+            bvh::BvhNode,
+            ray::Ray,
+            sphere::Sphere,
+            traceable::{Traceable, TraceableGroup},
+        },
+        types::{color::COLOR_BLACK, diffuse_mat::DiffuseMaterial, vec3::Vec3},
+        utils::{
+            config::Config,
+            render_stats::{flush_thread_counters, format_duration, ProgressReporter, RenderStats},
+            utilities::{seeded_generator, MAX_F64},
+        },
+    };
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(4200)), "4.2s");
+        assert_eq!(format_duration(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "1h 02m 03s");
+    }
+
+    #[test]
+    fn test_rays_are_counted() {
+        let mut rng = seeded_generator(0);
+        let floor = Sphere::new(
+            &Vec3::new(0.0, -100.5, -1.0),
+            100.0,
+            Box::new(DiffuseMaterial::new(&Vec3::new(0.5, 0.5, 0.5))),
+        );
+        let scene_objects = TraceableGroup {
+            objects: vec![Box::new(floor.unwrap())],
+        };
+
+        // Other tests may add to the totals concurrently, so only lower bounds hold
+        let before = RenderStats::collect(Duration::from_secs(1));
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, -1.0, 0.0));
+        for _ in 0..10 {
+            ray.ray_color(
+                &scene_objects,
+                &Background::Solid(COLOR_BLACK),
+                &Config::default(),
+                &mut rng,
+            );
+        }
+        let after = RenderStats::collect(Duration::from_secs(1));
+
+        assert!(after.primary_rays() >= before.primary_rays() + 10);
+        // Every ray towards the floor bounces at least once
+        assert!(after.secondary_rays() >= before.secondary_rays() + 10);
+        assert!(after.object_tests() >= before.object_tests() + 20);
+        assert!(after.average_path_depth() >= 1.0);
+        assert_eq!(after.total_rays(), after.primary_rays() + after.secondary_rays());
+    }
+
+    #[test]
+    fn test_object_tests_are_counted_once() {
+        let sphere = |z: f64| -> Box<dyn Traceable> {
+            Box::new(
+                Sphere::new(
+                    &Vec3::new(0.0, 0.0, z),
+                    0.5,
+                    Box::new(DiffuseMaterial::new(&Vec3::new(0.5, 0.5, 0.5))),
+                )
+                .unwrap(),
+            )
+        };
+        let nested_group = || TraceableGroup {
+            objects: vec![sphere(-2.0), sphere(-4.0)],
+        };
+        let scene_objects = BvhNode::from_group(TraceableGroup {
+            objects: vec![sphere(-6.0), Box::new(nested_group())],
+        })
+        .unwrap();
+
+        // Only the spheres count, not the group holding them or the BVH leaf
+        flush_thread_counters();
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 0.0, -1.0));
+        assert!(scene_objects.intersects_ray(&ray, 0.001, MAX_F64).is_some());
+        let stats = RenderStats::collect_thread(Duration::from_secs(1));
+        assert_eq!(stats.object_tests(), 3);
+        assert_eq!(stats.node_tests(), 1);
+
+        flush_thread_counters();
+        assert!(nested_group()
+            .intersects_ray(&ray, 0.001, MAX_F64)
+            .is_some());
+        let stats = RenderStats::collect_thread(Duration::from_secs(1));
+        assert_eq!(stats.object_tests(), 2);
+        assert_eq!(stats.node_tests(), 0);
+    }
+
+    #[test]
+    fn test_progress_reporter_counts_rows() {
+        let progress = ProgressReporter::new("Pass 1", 3, false);
+        for _ in 0..3 {
+            progress.row_done();
+        }
+        assert_eq!(progress.completed_rows(), 3);
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{background::Background, camera::Camera, renderer::Renderer},
+        types::{accumulation_buffer::AccumulationBuffer, color::Color, framebuffer::Framebuffer},
+        utils::{config::Config, scene::SceneDescription},
+    };
+
+    const SCENE: &str = r#"
+        [materials.matte]
+        type = "Diffuse"
+        albedo = [0.7, 0.3, 0.3]
+
+        [materials.glass]
+        type = "Dielectric"
+        ior = 1.5
+
+        [[objects]]
+        type = "Sphere"
+        center = [0.0, -100.5, -1.0]
+        radius = 100.0
+        material = "matte"
+
+        [[objects]]
+        type = "Sphere"
+        center = [0.0, 0.0, -1.0]
+        radius = 0.5
+        material = "glass"
+    "#;
+
+    fn render(threads: usize, seed: u64) -> Framebuffer {
+        let config = Config::from_toml_str(&format!(
+            r#"
+            [image]
+            image_width = 16
+            image_height = 12
+            aa = {{ mode = "MSAA" }}
+
+            [camera]
+            aperture = 0.1
+
+            [rays]
+
+            [render]
+            threads = {}
+            seed = {}
+            "#,
+            threads, seed
+        ));
+
+        let camera = Camera::configure(&config);
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+        let background = Background::configure(&config).unwrap();
+        Renderer::new(&camera, &scene_objects, &background, &config).render()
+    }
+
+    #[test]
+    fn test_render_is_independent_of_thread_count() {
+        let single_threaded = render(1, 1234);
+        assert_eq!(single_threaded.pixels().len(), 16 * 12);
+        assert_eq!(single_threaded.pixels(), render(3, 1234).pixels());
+        assert_eq!(single_threaded.pixels(), render(8, 1234).pixels());
+    }
+
+    #[test]
+    fn test_render_is_reproducible_from_seed() {
+        assert_eq!(render(2, 99).pixels(), render(2, 99).pixels());
+        assert_ne!(render(2, 99).pixels(), render(2, 100).pixels());
+    }
+
+    #[test]
+    fn test_adaptive_sampling() {
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+        let config = Config::from_toml_str(
+            r#"
+            [image]
+            image_width = 16
+            image_height = 12
+            aa = { mode = "Adaptive", min_samples = 4, max_samples = 64, threshold = 0.01 }
+
+            [camera]
+
+            [rays]
+
+            [render]
+            seed = 3
+
+            [background]
+            type = "Solid"
+            color = [0.5, 0.5, 0.5]
+            "#,
+        );
+
+        let camera = Camera::configure(&config);
+        let background = Background::configure(&config).unwrap();
+        let (image, sample_counts) = Renderer::new(&camera, &scene_objects, &background, &config)
+            .render_with_sample_counts();
+
+        // The background converges immediately, the glass ball needs more samples
+        assert_eq!(*image.pixel(0, 0), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(sample_counts.pixel(0, 0).x(), 4.0 / 64.0);
+        assert!(sample_counts
+            .pixels()
+            .iter()
+            .any(|samples| samples.x() > 4.0 / 64.0));
+        assert!(sample_counts
+            .pixels()
+            .iter()
+            .all(|samples| samples.x() <= 1.0));
+    }
+
+    #[test]
+    fn test_render_with_each_sampler() {
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        for sampler in ["Independent", "Stratified", "Halton", "Sobol"] {
+            let config = Config::from_toml_str(&format!(
+                r#"
+                [image]
+                image_width = 8
+                image_height = 6
+                aa = {{ mode = "MSAA", samples = 9 }}
+                sampler = "{}"
+
+                [camera]
+
+                [rays]
+
+                [render]
+                seed = 7
+                "#,
+                sampler
+            ));
+            assert_eq!(config.image_config().samples_per_pixel(), 9);
+
+            let camera = Camera::configure(&config);
+            let background = Background::configure(&config).unwrap();
+            let image = Renderer::new(&camera, &scene_objects, &background, &config).render();
+            assert!(image
+                .pixels()
+                .iter()
+                .all(|pixel| pixel.x().is_finite() && pixel.x() >= 0.0));
+        }
+    }
+
+    fn progressive_config() -> Config {
+        Config::from_toml_str(
+            r#"
+            [image]
+            image_width = 8
+            image_height = 6
+            aa = { mode = "MSAA", samples = 10 }
+
+            [camera]
+
+            [rays]
+
+            [render]
+            seed = 11
+            progressive = { pass_samples = 4 }
+            "#,
+        )
+    }
+
+    #[test]
+    fn test_progressive_passes() {
+        let config = progressive_config();
+        assert_eq!(config.render_config().progressive().unwrap().pass_samples(), 4);
+
+        let camera = Camera::configure(&config);
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+        let background = Background::configure(&config).unwrap();
+        let renderer = Renderer::new(&camera, &scene_objects, &background, &config);
+
+        let mut buffer = renderer.create_buffer();
+        let mut targets = vec![];
+        renderer.render_progressive(&mut buffer, 4, |buffer| {
+            targets.push(buffer.target_samples());
+            assert!(buffer
+                .pixels()
+                .iter()
+                .all(|pixel| pixel.samples() == buffer.target_samples()));
+        });
+
+        assert_eq!(targets, vec![4, 8, 10]);
+        assert_eq!(buffer.passes(), 3);
+
+        // A finished buffer needs no further passes
+        renderer.render_progressive(&mut buffer, 4, |_| panic!("Unexpected pass"));
+    }
+
+    #[test]
+    fn test_progressive_render_resumes() {
+        let config = progressive_config();
+        let camera = Camera::configure(&config);
+        let scene_objects = SceneDescription::from_toml_str(SCENE)
+            .unwrap()
+            .build()
+            .unwrap();
+        let background = Background::configure(&config).unwrap();
+        let renderer = Renderer::new(&camera, &scene_objects, &background, &config);
+
+        let mut uninterrupted = renderer.create_buffer();
+        renderer.render_progressive(&mut uninterrupted, 4, |_| {});
+
+        // Stop after the first pass, save the buffer and continue from the saved copy
+        let mut interrupted = renderer.create_buffer();
+        renderer.render_pass(&mut interrupted, 4);
+        let mut bytes = vec![];
+        interrupted.write_to(&mut bytes).unwrap();
+        let mut resumed = AccumulationBuffer::read_from(bytes.as_slice()).unwrap();
+        renderer.render_progressive(&mut resumed, 4, |_| {});
+
+        assert_eq!(resumed, uninterrupted);
+        assert_eq!(resumed.to_framebuffer().pixels(), uninterrupted.to_framebuffer().pixels());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::sampler::{
+            GridSampler, HaltonSampler, IndependentSampler, Sampler, SobolSampler,
+            StratifiedSampler,
+        },
+        utils::utilities::seeded_generator,
+    };
+
+    fn samples(sampler: &dyn Sampler, x: u64, y: u64, count: u64) -> Vec<(f64, f64)> {
+        let mut rng = seeded_generator(5);
+        (0..count)
+            .map(|index| sampler.pixel_sample(x, y, index, count, &mut rng))
+            .collect()
+    }
+
+    /// Number of samples in each cell of a grid over the pixel
+    fn cell_counts(samples: &[(f64, f64)], columns: usize, rows: usize) -> Vec<usize> {
+        let mut counts = vec![0; columns * rows];
+        for (u, v) in samples {
+            let column = (u * columns as f64) as usize;
+            let row = (v * rows as f64) as usize;
+            counts[row * columns + column] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_samples_are_inside_pixel() {
+        let samplers: Vec<Box<dyn Sampler>> = vec![
+            Box::new(IndependentSampler {}),
+            Box::new(StratifiedSampler {}),
+            Box::new(HaltonSampler::new(3)),
+            Box::new(SobolSampler::new(3)),
+        ];
+
+        for sampler in &samplers {
+            for (u, v) in samples(sampler.as_ref(), 4, 9, 50) {
+                assert!((0.0..1.0).contains(&u));
+                assert!((0.0..1.0).contains(&v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stratified_sampler() {
+        let stratified = samples(&StratifiedSampler {}, 0, 0, 16);
+        assert!(cell_counts(&stratified, 4, 4)
+            .iter()
+            .all(|&count| count == 1));
+
+        // The first samples are already spread over the whole pixel
+        assert!(cell_counts(&stratified[..4], 2, 2)
+            .iter()
+            .all(|&count| count == 1));
+
+        // Samples beyond the largest square grid are still inside the pixel
+        let stratified = samples(&StratifiedSampler {}, 0, 0, 20);
+        assert_eq!(cell_counts(&stratified, 4, 4).iter().sum::<usize>(), 20);
+    }
+
+    #[test]
+    fn test_grid_sampler() {
+        let grid = samples(&GridSampler {}, 0, 0, 9);
+        assert_eq!(grid[0], (1.0 / 6.0, 1.0 / 6.0));
+        assert_eq!(grid[4], (0.5, 0.5));
+        assert!(cell_counts(&grid, 3, 3).iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_low_discrepancy_samplers() {
+        assert_eq!(HaltonSampler::radical_inverse(2, 6), 0.375);
+        assert!((HaltonSampler::radical_inverse(3, 5) - 7.0 / 9.0).abs() < 1e-12);
+
+        // Shifted Halton points stay evenly spaced along each axis
+        let halton = samples(&HaltonSampler::new(11), 2, 3, 16);
+        assert!(cell_counts(&halton, 16, 1).iter().all(|&count| count == 1));
+
+        // The first power of two of scrambled Sobol points covers every grid cell once
+        let sobol = samples(&SobolSampler::new(11), 2, 3, 16);
+        assert!(cell_counts(&sobol, 4, 4).iter().all(|&count| count == 1));
+        assert!(cell_counts(&sobol, 16, 1).iter().all(|&count| count == 1));
+        assert!(cell_counts(&sobol, 2, 8).iter().all(|&count| count == 1));
+
+        // Neighbouring pixels get differently scrambled points
+        assert_ne!(sobol, samples(&SobolSampler::new(11), 3, 3, 16));
+        assert_eq!(sobol, samples(&SobolSampler::new(11), 2, 3, 16));
+    }
+}
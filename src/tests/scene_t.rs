@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::scene::{SceneDescription, SceneError};
+
+    const MATERIALS: &str = r#"
+        [materials.matte]
+        type = "Diffuse"
+        albedo = [0.7, 0.3, 0.3]
+
+        [materials.mirror]
+        type = "Metal"
+        albedo = [0.8, 0.8, 0.8]
+        roughness = 0.1
+
+        [materials.glass]
+        type = "Dielectric"
+        ior = 1.5
+    "#;
+
+    #[test]
+    fn test_build_scene() {
+        let scene_toml = format!(
+            "{}{}",
+            MATERIALS,
+            r#"
+            [[objects]]
+            type = "Sphere"
+            center = [0.0, 0.0, -1.0]
+            radius = 0.5
+            material = "matte"
+
+            [[objects]]
+            type = "Sphere"
+            center = [1.0, 0.0, -1.0]
+            radius = 0.5
+            material = "glass"
+            "#
+        );
+
+        let scene = SceneDescription::from_toml_str(&scene_toml)
+            .unwrap()
+            .build();
+        assert!(scene.is_ok());
+        assert_eq!(scene.unwrap().objects.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_material() {
+        let scene_toml = format!(
+            "{}{}",
+            MATERIALS,
+            r#"
+            [[objects]]
+            type = "Sphere"
+            name = "ball"
+            center = [0.0, 0.0, -1.0]
+            radius = 0.5
+            material = "chrome"
+            "#
+        );
+
+        let scene = SceneDescription::from_toml_str(&scene_toml)
+            .unwrap()
+            .build();
+        match scene {
+            Err(SceneError::UnknownMaterial { object, material }) => {
+                assert_eq!(object, "Object #0 ('ball')");
+                assert_eq!(material, "chrome");
+            }
+            _ => panic!("Expected an unknown material error"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_radius() {
+        let scene_toml = format!(
+            "{}{}",
+            MATERIALS,
+            r#"
+            [[objects]]
+            type = "Sphere"
+            center = [0.0, 0.0, -1.0]
+            radius = 0.5
+            material = "matte"
+
+            [[objects]]
+            type = "Sphere"
+            center = [0.0, 0.0, -1.0]
+            radius = -0.5
+            material = "mirror"
+            "#
+        );
+
+        let scene = SceneDescription::from_toml_str(&scene_toml)
+            .unwrap()
+            .build();
+        assert!(
+            matches!(scene, Err(SceneError::InvalidObject { ref object, .. }) if object == "Object #1")
+        );
+    }
+
+    #[test]
+    fn test_build_triangles() {
+        let scene_toml = format!(
+            "{}{}",
+            MATERIALS,
+            r#"
+            [[objects]]
+            type = "Triangle"
+            vertices = [[0.0, 0.0, -1.0], [1.0, 0.0, -1.0], [0.0, 1.0, -1.0]]
+            material = "matte"
+
+            [[objects]]
+            type = "Mesh"
+            vertices = [[0.0, 0.0, -1.0], [1.0, 0.0, -1.0], [1.0, 1.0, -1.0], [0.0, 1.0, -1.0]]
+            indices = [[0, 1, 2], [0, 2, 3]]
+            material = "mirror"
+
+            [[objects]]
+            type = "Mesh"
+            name = "broken"
+            vertices = [[0.0, 0.0, -1.0], [1.0, 0.0, -1.0], [1.0, 1.0, -1.0]]
+            indices = [[0, 1, 3]]
+            material = "mirror"
+            "#
+        );
+
+        let scene = SceneDescription::from_toml_str(&scene_toml)
+            .unwrap()
+            .build();
+        assert!(
+            matches!(scene, Err(SceneError::InvalidObject { ref object, .. }) if object == "Object #2 ('broken')")
+        );
+    }
+
+    #[test]
+    fn test_unknown_material_type() {
+        let scene = SceneDescription::from_toml_str(
+            r#"
+            [materials.plastic]
+            type = "Plastic"
+            albedo = [0.7, 0.3, 0.3]
+            "#,
+        );
+        assert!(matches!(scene, Err(SceneError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_textured_materials() {
+        let scene_toml = r#"
+            [textures.checker]
+            type = "Checker"
+            even = [1.0, 1.0, 1.0]
+            odd = [0.0, 0.0, 0.0]
+            scale = 4.0
+
+            [materials.floor]
+            type = "Diffuse"
+            albedo = "checker"
+
+            [materials.plain]
+            type = "Metal"
+            albedo = [0.8, 0.8, 0.8]
+
+            [[objects]]
+            type = "Triangle"
+            vertices = [[0.0, 0.0, -1.0], [1.0, 0.0, -1.0], [0.0, 1.0, -1.0]]
+            texture_coords = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]
+            material = "floor"
+
+            [[objects]]
+            type = "Sphere"
+            center = [0.0, 0.0, -1.0]
+            radius = 0.5
+            material = "plain"
+        "#;
+
+        let scene = SceneDescription::from_toml_str(scene_toml).unwrap().build();
+        assert_eq!(scene.unwrap().objects.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_textures() {
+        let scene = SceneDescription::from_toml_str(
+            r#"
+            [materials.floor]
+            type = "Diffuse"
+            albedo = "marble"
+            "#,
+        )
+        .unwrap()
+        .build();
+        match scene {
+            Err(SceneError::UnknownTexture { material, texture }) => {
+                assert_eq!(material, "floor");
+                assert_eq!(texture, "marble");
+            }
+            _ => panic!("Expected an unknown texture error"),
+        }
+
+        let scene = SceneDescription::from_toml_str(
+            r#"
+            [textures.photo]
+            type = "Image"
+            file = "does/not/exist.png"
+            "#,
+        )
+        .unwrap()
+        .build();
+        assert!(
+            matches!(scene, Err(SceneError::InvalidTexture { ref texture, .. }) if texture == "photo")
+        );
+    }
+
+    #[test]
+    fn test_model_without_faces() {
+        let obj_file = std::env::temp_dir().join(format!("scene_t_{}.obj", std::process::id()));
+        std::fs::write(&obj_file, "v 0 0 0\nv 1 0 0\n").unwrap();
+
+        let scene_toml = format!(
+            r#"{}
+            [[objects]]
+            type = "Model"
+            file = "{}"
+            material = "matte"
+            "#,
+            MATERIALS,
+            obj_file.display()
+        );
+        let scene = SceneDescription::from_toml_str(&scene_toml)
+            .unwrap()
+            .build();
+        std::fs::remove_file(&obj_file).unwrap();
+
+        assert!(scene.unwrap().objects.is_empty());
+    }
+
+    #[test]
+    fn test_paths_relative_to_scene() {
+        let directory = std::env::temp_dir().join(format!("scene_t_{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("models")).unwrap();
+        std::fs::write(
+            directory.join("models/triangle.obj"),
+            "mtllib triangle.mtl\nusemtl white\nv 0 0 -1\nv 1 0 -1\nv 0 1 -1\nf 1 2 3\n",
+        )
+        .unwrap();
+        std::fs::write(directory.join("models/triangle.mtl"), "newmtl white\nKd 1 1 1\n").unwrap();
+        std::fs::write(
+            directory.join("scene.toml"),
+            "[[objects]]\ntype = \"Model\"\nfile = \"models/triangle.obj\"\n",
+        )
+        .unwrap();
+
+        let scene = SceneDescription::from_toml(directory.join("scene.toml").to_str().unwrap())
+            .unwrap()
+            .build();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(scene.unwrap().objects.len(), 1);
+    }
+}
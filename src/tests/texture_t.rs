@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{ray::Ray, sphere::Sphere, traceable::Traceable},
+        types::{
+            checker_tex::{CheckerTexture, UvCheckerTexture},
+            color::{Color, COLOR_BLACK, COLOR_WHITE},
+            diffuse_mat::DiffuseMaterial,
+            framebuffer::Framebuffer,
+            image_tex::ImageTexture,
+            solid_tex::SolidTexture,
+            texture::Texture,
+            vec3::Vec3,
+        },
+        utils::{
+            image_reader::{read_png, read_ppm},
+            image_writer::write_png,
+            utilities::MAX_F64,
+        },
+    };
+
+    #[test]
+    fn test_solid_and_checker_textures() {
+        let origin = Vec3::zero_vec();
+        let solid = SolidTexture::new(&Color::new(0.1, 0.2, 0.3));
+        assert_eq!(solid.value(0.7, 0.2, &origin), Color::new(0.1, 0.2, 0.3));
+
+        let checker = CheckerTexture::new(&COLOR_WHITE, &COLOR_BLACK, 2.0);
+        assert_eq!(checker.value(0.0, 0.0, &Vec3::new(0.1, 0.1, 0.1)), COLOR_WHITE);
+        assert_eq!(checker.value(0.0, 0.0, &Vec3::new(0.6, 0.1, 0.1)), COLOR_BLACK);
+        assert_eq!(checker.value(0.0, 0.0, &Vec3::new(-0.1, 0.1, 0.1)), COLOR_BLACK);
+
+        let uv_checker = UvCheckerTexture::new(&COLOR_WHITE, &COLOR_BLACK, 4.0);
+        assert_eq!(uv_checker.value(0.1, 0.1, &origin), COLOR_WHITE);
+        assert_eq!(uv_checker.value(0.3, 0.1, &origin), COLOR_BLACK);
+        assert_eq!(uv_checker.value(0.3, 0.3, &origin), COLOR_WHITE);
+    }
+
+    #[test]
+    fn test_image_texture() {
+        // Red top row, blue bottom row
+        let mut image = Framebuffer::new(2, 2);
+        for x in 0..2 {
+            image.set_pixel(x, 0, &Color::new(1.0, 0.0, 0.0));
+            image.set_pixel(x, 1, &Color::new(0.0, 0.0, 1.0));
+        }
+
+        let texture = ImageTexture::new(image).unwrap();
+        let origin = Vec3::zero_vec();
+        assert_eq!(texture.value(0.5, 0.9, &origin), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(texture.value(0.5, 0.1, &origin), Color::new(0.0, 0.0, 1.0));
+        // Coordinates outside of [0, 1] repeat the image
+        assert_eq!(texture.value(1.5, 1.1, &origin), Color::new(0.0, 0.0, 1.0));
+
+        assert!(ImageTexture::new(Framebuffer::new(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_read_ppm() {
+        let plain = b"P3\n# comment\n2 1\n255\n255 0 0  0 0 255\n";
+        let image = read_ppm(plain, 1.0).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(*image.pixel(0, 0), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(*image.pixel(1, 0), Color::new(0.0, 0.0, 1.0));
+
+        let mut binary = b"P6\n1 1\n255\n".to_vec();
+        binary.extend_from_slice(&[0, 255, 0]);
+        let image = read_ppm(&binary, 2.0).unwrap();
+        assert_eq!(*image.pixel(0, 0), Color::new(0.0, 1.0, 0.0));
+
+        assert!(read_ppm(b"P6\n2 2\n255\n\0\0\0", 1.0).is_err());
+        assert!(read_ppm(b"P6\n18446744073709551615 2\n255\n\0\0\0", 1.0).is_err());
+        assert!(read_ppm(b"P3\n4294967296 4294967296\n255\n0 0 0", 1.0).is_err());
+
+        // Samples above the maximum value cannot be rescaled
+        let mut binary = b"P6\n1 1\n100\n".to_vec();
+        binary.extend_from_slice(&[200, 50, 100]);
+        assert!(read_ppm(&binary, 1.0).is_err());
+        assert!(read_ppm(b"P3\n1 1\n100\n100 50 101\n", 1.0).is_err());
+        let image = read_ppm(b"P3\n1 1\n100\n100 0 100\n", 1.0).unwrap();
+        assert_eq!(*image.pixel(0, 0), Color::new(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_read_png() {
+        let mut image = Framebuffer::new(3, 2);
+        image.set_pixel(0, 0, &COLOR_WHITE);
+        image.set_pixel(2, 1, &Color::new(0.0, 1.0, 0.0));
+
+        let mut png_data: Vec<u8> = vec![];
+        write_png(&image, &mut png_data, 2.0).unwrap();
+
+        let decoded = read_png(&png_data, 2.0).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+        assert_eq!(*decoded.pixel(1, 0), COLOR_BLACK);
+        assert!((*decoded.pixel(0, 0) - COLOR_WHITE).len() < 0.01);
+        assert!((*decoded.pixel(2, 1) - Color::new(0.0, 1.0, 0.0)).len() < 0.01);
+    }
+
+    #[test]
+    fn test_sphere_uv() {
+        assert_eq!(Sphere::surface_uv(&Vec3::new(-1.0, 0.0, 0.0)), (0.0, 0.5));
+        assert_eq!(Sphere::surface_uv(&Vec3::new(1.0, 0.0, 0.0)), (0.5, 0.5));
+        assert_eq!(Sphere::surface_uv(&Vec3::new(0.0, 1.0, 0.0)).1, 1.0);
+        assert_eq!(Sphere::surface_uv(&Vec3::new(0.0, -1.0, 0.0)).1, 0.0);
+
+        let sphere = Sphere::new(
+            &Vec3::new(0.0, 0.0, -2.0),
+            1.0,
+            Box::new(DiffuseMaterial::new(&COLOR_WHITE)),
+        )
+        .unwrap();
+        let ray = Ray::new(&Vec3::zero_vec(), &Vec3::new(0.0, 0.0, -1.0));
+        let hit = sphere.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((hit.u() - 0.25).abs() < 1e-9);
+        assert!((hit.v() - 0.5).abs() < 1e-9);
+    }
+}
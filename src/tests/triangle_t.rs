@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    const DOUBLE_TEST_EPSILON: f64 = 0.00001;
+    use crate::{
+        components::{
+            ray::Ray,
+            traceable::Traceable,
+            triangle::{intersect_triangle, Triangle},
+            triangle_mesh::TriangleMesh,
+        },
+        types::{color::Color, diffuse_mat::DiffuseMaterial, material::Material, vec3::Vec3},
+        utils::utilities::MAX_F64,
+    };
+
+    fn material() -> Box<dyn Material> {
+        Box::new(DiffuseMaterial::new(&Color::new(0.5, 0.5, 0.5)))
+    }
+
+    fn vertices() -> [Vec3; 3] {
+        [
+            Vec3::new(0.0, 0.0, -2.0),
+            Vec3::new(2.0, 0.0, -2.0),
+            Vec3::new(0.0, 2.0, -2.0),
+        ]
+    }
+
+    #[test]
+    fn test_degenerate_triangle() {
+        let collinear = [
+            Vec3::new(0.0, 0.0, -2.0),
+            Vec3::new(1.0, 1.0, -2.0),
+            Vec3::new(2.0, 2.0, -2.0),
+        ];
+        assert!(Triangle::new(collinear, material()).is_err());
+        assert!(Triangle::new(vertices(), material()).is_ok());
+    }
+
+    #[test]
+    fn test_intersects_ray() {
+        let triangle = Triangle::new(vertices(), material()).unwrap();
+
+        let ray = Ray::new(&Vec3::new(0.5, 0.5, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((hit_record.trace() - 2.0).abs() <= DOUBLE_TEST_EPSILON);
+        assert!(hit_record.is_front_facing());
+        assert_eq!(hit_record.normal(), &Vec3::new(0.0, 0.0, 1.0));
+
+        // Hit from behind flips the normal towards the ray
+        let ray = Ray::new(&Vec3::new(0.5, 0.5, -4.0), &Vec3::new(0.0, 0.0, 1.0));
+        let hit_record = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!(!hit_record.is_front_facing());
+        assert_eq!(hit_record.normal(), &Vec3::new(0.0, 0.0, -1.0));
+
+        // Outside the triangle, beyond the trace range and parallel to the plane
+        let ray = Ray::new(&Vec3::new(1.5, 1.5, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        assert!(triangle.intersects_ray(&ray, 0.0, MAX_F64).is_none());
+        let ray = Ray::new(&Vec3::new(0.5, 0.5, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        assert!(triangle.intersects_ray(&ray, 0.0, 1.0).is_none());
+        let ray = Ray::new(&Vec3::new(0.5, 0.5, 0.0), &Vec3::new(1.0, 0.0, 0.0));
+        assert!(triangle.intersects_ray(&ray, 0.0, MAX_F64).is_none());
+    }
+
+    #[test]
+    fn test_barycentric_coordinates() {
+        let ray = Ray::new(&Vec3::new(1.0, 0.5, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let (_, barycentric) = intersect_triangle(&ray, &vertices(), 0.0, MAX_F64).unwrap();
+        assert!((barycentric[0] - 0.25).abs() <= DOUBLE_TEST_EPSILON);
+        assert!((barycentric[1] - 0.5).abs() <= DOUBLE_TEST_EPSILON);
+        assert!((barycentric[2] - 0.25).abs() <= DOUBLE_TEST_EPSILON);
+    }
+
+    #[test]
+    fn test_smooth_normals() {
+        let normals = [
+            Vec3::new(-1.0, 0.0, 1.0).unit_vector().unwrap(),
+            Vec3::new(1.0, 0.0, 1.0).unit_vector().unwrap(),
+            Vec3::new(0.0, 0.0, 1.0),
+        ];
+        let triangle = Triangle::with_normals(vertices(), normals, material()).unwrap();
+
+        // Halfway between the first two vertices the x components cancel out
+        let ray = Ray::new(&Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((*hit_record.normal() - Vec3::new(0.0, 0.0, 1.0)).len() <= DOUBLE_TEST_EPSILON);
+
+        // Near the second vertex the normal leans towards +x
+        let ray = Ray::new(&Vec3::new(1.9, 0.05, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!(hit_record.normal().x() > 0.5);
+    }
+
+    #[test]
+    fn test_mesh() {
+        let vertices = vec![
+            Vec3::new(-1.0, -1.0, -2.0),
+            Vec3::new(1.0, -1.0, -2.0),
+            Vec3::new(1.0, 1.0, -2.0),
+            Vec3::new(-1.0, 1.0, -2.0),
+        ];
+        let indices = vec![[0, 1, 2], [0, 2, 3]];
+
+        let mesh = TriangleMesh::new(vertices.clone(), None, indices.clone(), material()).unwrap();
+        assert_eq!(mesh.triangle_count(), 2);
+
+        let bounding_box = mesh.bounding_box().unwrap();
+        assert!(bounding_box.minimum().x() <= -1.0 && bounding_box.maximum().y() >= 1.0);
+
+        for (x, y) in [(0.5, -0.5), (-0.5, 0.5), (0.9, 0.9), (-0.9, -0.9)] {
+            let ray = Ray::new(&Vec3::new(x, y, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+            assert!(mesh.intersects_ray(&ray, 0.0, MAX_F64).is_some());
+        }
+        let ray = Ray::new(&Vec3::new(1.5, 0.0, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        assert!(mesh.intersects_ray(&ray, 0.0, MAX_F64).is_none());
+
+        assert!(TriangleMesh::new(vertices.clone(), None, vec![[0, 1, 4]], material()).is_err());
+        assert!(
+            TriangleMesh::new(vertices, Some(vec![Vec3::zero_vec()]), indices, material()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_texture_coords() {
+        let ray = Ray::new(&Vec3::new(0.5, 1.0, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+
+        // Without texture coordinates, u and v are barycentric weights
+        let mut triangle = Triangle::new(vertices(), material()).unwrap();
+        let hit = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((hit.u() - 0.25).abs() < DOUBLE_TEST_EPSILON);
+        assert!((hit.v() - 0.5).abs() < DOUBLE_TEST_EPSILON);
+
+        triangle.set_texture_coords([(1.0, 1.0), (0.0, 1.0), (1.0, 0.0)]);
+        let hit = triangle.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((hit.u() - 0.75).abs() < DOUBLE_TEST_EPSILON);
+        assert!((hit.v() - 0.5).abs() < DOUBLE_TEST_EPSILON);
+
+        let mesh = TriangleMesh::with_texture_coords(
+            vertices().to_vec(),
+            None,
+            Some(vec![(1.0, 1.0), (0.0, 1.0), (1.0, 0.0)]),
+            vec![[0, 1, 2]],
+            material(),
+        )
+        .unwrap();
+        let hit = mesh.intersects_ray(&ray, 0.0, MAX_F64).unwrap();
+        assert!((hit.u() - 0.75).abs() < DOUBLE_TEST_EPSILON);
+        assert!((hit.v() - 0.5).abs() < DOUBLE_TEST_EPSILON);
+
+        assert!(TriangleMesh::with_texture_coords(
+            vertices().to_vec(),
+            None,
+            Some(vec![(0.0, 0.0)]),
+            vec![[0, 1, 2]],
+            material(),
+        )
+        .is_err());
+    }
+}
@@ -1,7 +1,16 @@
+pub mod aabb;
+pub mod accumulation_buffer;
+pub mod checker_tex;
 pub mod color;
 pub mod dielectric_mat;
+pub mod diffuse_light;
 pub mod diffuse_mat;
+pub mod framebuffer;
 pub mod hit_record;
+pub mod image_tex;
 pub mod material;
 pub mod metal_mat;
+pub mod noise_tex;
+pub mod solid_tex;
+pub mod texture;
 pub mod vec3;
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use crate::components::ray::Ray;
+use crate::utils::utilities::{fmax, fmin};
+
+use super::hit_record::Point3;
+
+/// Axis-aligned bounding box between two corner points
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    d_minimum: Point3,
+    d_maximum: Point3,
+}
+
+impl Aabb {
+    pub fn new(minimum: &Point3, maximum: &Point3) -> Self {
+        Aabb {
+            d_minimum: *minimum,
+            d_maximum: *maximum,
+        }
+    }
+
+    /// Smallest box containing both boxes
+    pub fn surrounding_box(box_0: &Aabb, box_1: &Aabb) -> Self {
+        Aabb {
+            d_minimum: Point3::new(
+                fmin(box_0.d_minimum.x(), box_1.d_minimum.x()),
+                fmin(box_0.d_minimum.y(), box_1.d_minimum.y()),
+                fmin(box_0.d_minimum.z(), box_1.d_minimum.z()),
+            ),
+            d_maximum: Point3::new(
+                fmax(box_0.d_maximum.x(), box_1.d_maximum.x()),
+                fmax(box_0.d_maximum.y(), box_1.d_maximum.y()),
+                fmax(box_0.d_maximum.z(), box_1.d_maximum.z()),
+            ),
+        }
+    }
+
+    pub fn minimum(&self) -> &Point3 {
+        &self.d_minimum
+    }
+
+    pub fn maximum(&self) -> &Point3 {
+        &self.d_maximum
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        (self.d_minimum + self.d_maximum).scaled(0.5)
+    }
+
+    /// Coordinate of the box minimum along the axis (0 = x, 1 = y, 2 = z)
+    pub fn axis_minimum(&self, axis: usize) -> f64 {
+        Aabb::axis_value(&self.d_minimum, axis)
+    }
+
+    pub fn axis_maximum(&self, axis: usize) -> f64 {
+        Aabb::axis_value(&self.d_maximum, axis)
+    }
+
+    /// Whether the ray passes through the box between min_trace and max_trace
+    pub fn intersects_ray(&self, ray: &Ray, min_trace: f64, max_trace: f64) -> bool {
+        let mut min_trace = min_trace;
+        let mut max_trace = max_trace;
+
+        for axis in 0..3 {
+            let inverse_direction = 1.0 / Aabb::axis_value(ray.direction(), axis);
+            let origin = Aabb::axis_value(ray.origin(), axis);
+
+            let mut t0 = (self.axis_minimum(axis) - origin) * inverse_direction;
+            let mut t1 = (self.axis_maximum(axis) - origin) * inverse_direction;
+            if inverse_direction < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            min_trace = fmax(t0, min_trace);
+            max_trace = fmin(t1, max_trace);
+            if max_trace < min_trace {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn axis_value(point: &Point3, axis: usize) -> f64 {
+        match axis {
+            0 => point.x(),
+            1 => point.y(),
+            _ => point.z(),
+        }
+    }
+}
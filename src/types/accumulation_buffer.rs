@@ -0,0 +1,270 @@
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use super::{
+    color::{luminance, Color},
+    framebuffer::Framebuffer,
+};
+
+// Identifies accumulation buffer files and their layout version
+const FILE_MAGIC: &[u8; 8] = b"RTACC001";
+// Size of a saved pixel: three sums, the sample count and two luminance moments
+const PIXEL_BYTES: usize = 6 * 8;
+
+/// Running sums of the samples taken for one pixel
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PixelAccumulator {
+    d_sum: Color,
+    d_samples: u64,
+    // Running mean and sum of squared deviations of the sample luminance (Welford)
+    d_luminance_mean: f64,
+    d_luminance_deviation: f64,
+}
+
+impl PixelAccumulator {
+    pub fn new() -> Self {
+        PixelAccumulator {
+            d_sum: Color::zero_vec(),
+            d_samples: 0,
+            d_luminance_mean: 0.0,
+            d_luminance_deviation: 0.0,
+        }
+    }
+
+    pub fn add_sample(&mut self, color: &Color) {
+        self.d_sum += *color;
+        self.d_samples += 1;
+
+        let sample_luminance = luminance(color);
+        let delta = sample_luminance - self.d_luminance_mean;
+        self.d_luminance_mean += delta / self.d_samples as f64;
+        self.d_luminance_deviation += delta * (sample_luminance - self.d_luminance_mean);
+    }
+
+    pub fn samples(&self) -> u64 {
+        self.d_samples
+    }
+
+    /// Average of the samples, black if there are none
+    pub fn mean(&self) -> Color {
+        if self.d_samples == 0 {
+            return Color::zero_vec();
+        }
+
+        self.d_sum.scaled(1.0 / self.d_samples as f64)
+    }
+
+    pub fn luminance_mean(&self) -> f64 {
+        self.d_luminance_mean
+    }
+
+    /// Standard error of the mean luminance, infinite with fewer than two samples
+    pub fn standard_error(&self) -> f64 {
+        if self.d_samples < 2 {
+            return f64::INFINITY;
+        }
+
+        let variance = self.d_luminance_deviation / (self.d_samples - 1) as f64;
+        (variance / self.d_samples as f64).sqrt()
+    }
+}
+
+impl Default for PixelAccumulator {
+    fn default() -> Self {
+        PixelAccumulator::new()
+    }
+}
+
+/// Pixel accumulators of a render in progress, stored row by row from the top of the
+/// image. Saving and loading it lets an interrupted render resume where it stopped
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccumulationBuffer {
+    d_width: usize,
+    d_height: usize,
+    d_seed: u64,
+    d_passes: u64,
+    d_target_samples: u64,
+    d_pixels: Vec<PixelAccumulator>,
+}
+
+impl AccumulationBuffer {
+    /********* Initializers *********/
+
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        AccumulationBuffer {
+            d_width: width,
+            d_height: height,
+            d_seed: seed,
+            d_passes: 0,
+            d_target_samples: 0,
+            d_pixels: vec![PixelAccumulator::new(); width * height],
+        }
+    }
+
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        AccumulationBuffer::read_from(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read_from<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != FILE_MAGIC {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not an accumulation buffer file",
+            ));
+        }
+
+        let mut read_u64 = || -> std::io::Result<u64> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+
+        let width = read_u64()? as usize;
+        let height = read_u64()? as usize;
+        let seed = read_u64()?;
+        let passes = read_u64()?;
+        let target_samples = read_u64()?;
+
+        // Check the size in the header against the pixel data before allocating it
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let data_size = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(PIXEL_BYTES));
+        if data_size != Some(data.len()) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Accumulation buffer size does not match its pixel data",
+            ));
+        }
+
+        let mut buffer = AccumulationBuffer::new(width, height, seed);
+        buffer.d_passes = passes;
+        buffer.d_target_samples = target_samples;
+
+        let mut values = data
+            .chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+        let mut read_u64 = || values.next().unwrap();
+        for pixel in buffer.d_pixels.iter_mut() {
+            let sum_x = f64::from_bits(read_u64());
+            let sum_y = f64::from_bits(read_u64());
+            let sum_z = f64::from_bits(read_u64());
+            *pixel = PixelAccumulator {
+                d_sum: Color::new(sum_x, sum_y, sum_z),
+                d_samples: read_u64(),
+                d_luminance_mean: f64::from_bits(read_u64()),
+                d_luminance_deviation: f64::from_bits(read_u64()),
+            };
+        }
+
+        Ok(buffer)
+    }
+
+    /************ Getters ************/
+
+    pub fn width(&self) -> usize {
+        self.d_width
+    }
+
+    pub fn height(&self) -> usize {
+        self.d_height
+    }
+
+    /// Seed of the render, kept so that resumed renders continue the same sequences
+    pub fn seed(&self) -> u64 {
+        self.d_seed
+    }
+
+    pub fn passes(&self) -> u64 {
+        self.d_passes
+    }
+
+    /// Samples per pixel that every unconverged pixel has reached
+    pub fn target_samples(&self) -> u64 {
+        self.d_target_samples
+    }
+
+    pub fn pixels(&self) -> &[PixelAccumulator] {
+        &self.d_pixels
+    }
+
+    pub fn row(&self, y: usize) -> &[PixelAccumulator] {
+        &self.d_pixels[y * self.d_width..(y + 1) * self.d_width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [PixelAccumulator] {
+        &mut self.d_pixels[y * self.d_width..(y + 1) * self.d_width]
+    }
+
+    /// Record a completed pass that brought every pixel to `target_samples`
+    pub fn finish_pass(&mut self, target_samples: u64) {
+        self.d_passes += 1;
+        self.d_target_samples = target_samples;
+    }
+
+    /// Current average color of every pixel
+    pub fn to_framebuffer(&self) -> Framebuffer {
+        self.map_pixels(|pixel| pixel.mean())
+    }
+
+    /// Grayscale image of the samples taken per pixel, white being `max_samples`
+    pub fn sample_counts(&self, max_samples: u64) -> Framebuffer {
+        self.map_pixels(|pixel| {
+            Color::ones_vec().scaled(pixel.samples() as f64 / max_samples as f64)
+        })
+    }
+
+    fn map_pixels(&self, pixel_color: impl Fn(&PixelAccumulator) -> Color) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(self.d_width, self.d_height);
+        for (index, pixel) in self.d_pixels.iter().enumerate() {
+            framebuffer.set_pixel(index % self.d_width, index / self.d_width, &pixel_color(pixel));
+        }
+        framebuffer
+    }
+
+    /************ Writers ************/
+
+    /// Save the buffer through a temporary file, so that an interrupted save keeps the
+    /// previous file intact
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let temporary_path = format!("{}.tmp", path);
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        self.write_to(&mut writer)?;
+        writer.into_inner()?.sync_all()?;
+
+        std::fs::rename(&temporary_path, path)
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(FILE_MAGIC)?;
+        for value in [
+            self.d_width as u64,
+            self.d_height as u64,
+            self.d_seed,
+            self.d_passes,
+            self.d_target_samples,
+        ] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        for pixel in &self.d_pixels {
+            for value in [
+                pixel.d_sum.x().to_bits(),
+                pixel.d_sum.y().to_bits(),
+                pixel.d_sum.z().to_bits(),
+                pixel.d_samples,
+                pixel.d_luminance_mean.to_bits(),
+                pixel.d_luminance_deviation.to_bits(),
+            ] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+}
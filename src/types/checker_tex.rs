@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+use super::{color::Color, hit_record::Point3, texture::Texture};
+
+/// Checkerboard of unit cubes in space, `scale` cubes per world unit
+#[derive(Debug, Clone)]
+pub struct CheckerTexture {
+    d_even: Color,
+    d_odd: Color,
+    d_scale: f64,
+}
+
+impl CheckerTexture {
+    pub fn new(even: &Color, odd: &Color, scale: f64) -> Self {
+        CheckerTexture {
+            d_even: *even,
+            d_odd: *odd,
+            d_scale: scale,
+        }
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, _u: f64, _v: f64, point: &Point3) -> Color {
+        let cell = (self.d_scale * point.x()).floor()
+            + (self.d_scale * point.y()).floor()
+            + (self.d_scale * point.z()).floor();
+
+        if (cell as i64).rem_euclid(2) == 0 {
+            self.d_even
+        } else {
+            self.d_odd
+        }
+    }
+}
+
+/// Checkerboard in texture space, `scale` squares along each of u and v
+#[derive(Debug, Clone)]
+pub struct UvCheckerTexture {
+    d_even: Color,
+    d_odd: Color,
+    d_scale: f64,
+}
+
+impl UvCheckerTexture {
+    pub fn new(even: &Color, odd: &Color, scale: f64) -> Self {
+        UvCheckerTexture {
+            d_even: *even,
+            d_odd: *odd,
+            d_scale: scale,
+        }
+    }
+}
+
+impl Texture for UvCheckerTexture {
+    fn value(&self, u: f64, v: f64, _point: &Point3) -> Color {
+        let cell = (self.d_scale * u).floor() + (self.d_scale * v).floor();
+
+        if (cell as i64).rem_euclid(2) == 0 {
+            self.d_even
+        } else {
+            self.d_odd
+        }
+    }
+}
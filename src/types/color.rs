@@ -7,19 +7,31 @@ pub type Color = Vec3;
 pub const COLOR_BLACK: Color = Color::new(0.0, 0.0, 0.0);
 pub const COLOR_WHITE: Color = Color::new(1.0, 1.0, 1.0);
 
-pub fn print_color(color: &Color, samples_per_pixel: u64, gamma: f64) {
-    let scale = 1.0 / (samples_per_pixel as f64);
+/// Perceived brightness of a linear color (Rec. 709 weights)
+pub fn luminance(color: &Color) -> f64 {
+    0.2126 * color.x() + 0.7152 * color.y() + 0.0722 * color.z()
+}
 
+/// Gamma correct a linear color and quantize it to 8 bits per channel
+pub fn to_rgb8(color: &Color, gamma: f64) -> [u8; 3] {
     let c = Color::new(
-        (color.x() * scale).powf(1.0 / gamma),
-        (color.y() * scale).powf(1.0 / gamma),
-        (color.z() * scale).powf(1.0 / gamma),
+        color.x().powf(1.0 / gamma),
+        color.y().powf(1.0 / gamma),
+        color.z().powf(1.0 / gamma),
     );
 
-    print!(
-        "{} {} {}\t",
-        (256.0 * clamp(c.x(), 0.0, 0.999)) as usize,
-        (256.0 * clamp(c.y(), 0.0, 0.999)) as usize,
-        (256.0 * clamp(c.z(), 0.0, 0.999)) as usize
+    [
+        (256.0 * clamp(c.x(), 0.0, 0.999)) as u8,
+        (256.0 * clamp(c.y(), 0.0, 0.999)) as u8,
+        (256.0 * clamp(c.z(), 0.0, 0.999)) as u8,
+    ]
+}
+
+/// Linear color of an 8-bit gamma encoded pixel, the inverse of `to_rgb8`
+pub fn from_rgb8(rgb: &[u8], gamma: f64) -> Color {
+    Color::new(
+        (rgb[0] as f64 / 255.0).powf(gamma),
+        (rgb[1] as f64 / 255.0).powf(gamma),
+        (rgb[2] as f64 / 255.0).powf(gamma),
     )
 }
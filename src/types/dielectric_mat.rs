@@ -2,7 +2,7 @@
 
 use crate::components::ray::Ray;
 use crate::utils::config::Config;
-use crate::utils::utilities::{fmin, random_f64};
+use crate::utils::utilities::{fmin, random_f64, RandomGenerator};
 
 use super::color::{Color, COLOR_WHITE};
 use super::hit_record::HitRecord;
@@ -30,7 +30,7 @@ impl DielectricMaterial {
         // to address varying reflectivity by ray angle
         let r0 = (1.0 - relative_ior) / (1.0 + relative_ior);
         let r0 = r0 * r0;
-        return r0 + (1.0 - r0) * ((1.0 - cos_theta).powi(5));
+        r0 + (1.0 - r0) * ((1.0 - cos_theta).powi(5))
     }
 }
 
@@ -40,6 +40,7 @@ impl Material for DielectricMaterial {
         parent_ray: &Ray,
         hit_record: &HitRecord,
         _scene_config: &Config,
+        rng: &mut RandomGenerator,
     ) -> Option<(Ray, Color)> {
         let refraction_ratio = if hit_record.is_front_facing() {
             1.0 / self.d_ior
@@ -48,33 +49,30 @@ impl Material for DielectricMaterial {
         };
 
         let cos_theta = fmin(
-            hit_record
+            -hit_record
                 .normal()
-                .dot(&parent_ray.direction().unit_vector().unwrap())
-                * -1.0,
+                .dot(&parent_ray.direction().unit_vector().unwrap()),
             1.0,
         );
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = refraction_ratio * sin_theta > 1.0;
 
-        let new_ray_direction: Vec3;
-
-        if cannot_refract
-            || DielectricMaterial::reflectance(cos_theta, refraction_ratio) > random_f64()
+        let new_ray_direction: Vec3 = if cannot_refract
+            || DielectricMaterial::reflectance(cos_theta, refraction_ratio) > random_f64(rng)
         {
-            new_ray_direction = parent_ray
+            parent_ray
                 .direction()
                 .unit_vector()
                 .unwrap()
-                .reflect(hit_record.normal());
+                .reflect(hit_record.normal())
         } else {
-            new_ray_direction = parent_ray
+            parent_ray
                 .direction()
                 .unit_vector()
                 .unwrap()
-                .refract(hit_record.normal(), refraction_ratio);
-        }
+                .refract(hit_record.normal(), refraction_ratio)
+        };
 
         let scattered_ray = Ray::new(hit_record.point(), &new_ray_direction);
         Some((scattered_ray, COLOR_WHITE))
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+use crate::components::ray::Ray;
+use crate::utils::{config::Config, utilities::RandomGenerator};
+
+use super::{color::Color, hit_record::HitRecord, material::Material};
+
+/// Material that emits light evenly in all directions and reflects none
+#[derive(Clone)]
+pub struct DiffuseLight {
+    d_emit: Color,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: &Color) -> Self {
+        DiffuseLight { d_emit: *emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(
+        &self,
+        _parent_ray: &Ray,
+        _hit_record: &HitRecord,
+        _scene_config: &Config,
+        _rng: &mut RandomGenerator,
+    ) -> Option<(Ray, Color)> {
+        None
+    }
+
+    fn emitted(&self, _hit_record: &HitRecord) -> Color {
+        self.d_emit
+    }
+}
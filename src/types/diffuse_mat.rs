@@ -1,18 +1,27 @@
 #[allow(dead_code)]
+use std::sync::Arc;
+
 use crate::components::ray::Ray;
 use crate::utils::config::{Config, DiffuseScatterMode};
-use crate::utils::utilities::{random_point_in_hemisphere, random_point_in_unit_sphere};
+use crate::utils::utilities::{
+    random_point_in_hemisphere, random_point_in_unit_sphere, RandomGenerator,
+};
 
-use super::{color::Color, material::Material};
+use super::{color::Color, material::Material, solid_tex::SolidTexture, texture::Texture};
 
 #[derive(Clone)]
 pub struct DiffuseMaterial {
-    d_albedo: Color,
+    d_albedo: Arc<dyn Texture>,
 }
 
 impl DiffuseMaterial {
     pub fn new(albedo: &Color) -> Self {
-        DiffuseMaterial { d_albedo: *albedo }
+        DiffuseMaterial::from_texture(Arc::new(SolidTexture::new(albedo)))
+    }
+
+    /// Create a material whose albedo varies over the surface
+    pub fn from_texture(albedo: Arc<dyn Texture>) -> Self {
+        DiffuseMaterial { d_albedo: albedo }
     }
 }
 
@@ -22,11 +31,16 @@ impl Material for DiffuseMaterial {
         _parent_ray: &crate::components::ray::Ray,
         hit_record: &super::hit_record::HitRecord,
         scene_config: &Config,
+        rng: &mut RandomGenerator,
     ) -> Option<(Ray, Color)> {
         let diffuse_scatter_mode = match scene_config.rays_config().diffuse_scatter_mode() {
-            DiffuseScatterMode::ApproxLambert => random_point_in_unit_sphere(),
-            DiffuseScatterMode::TrueLambert => random_point_in_unit_sphere().unit_vector().unwrap(),
-            DiffuseScatterMode::Hemispherical => random_point_in_hemisphere(hit_record.normal()),
+            DiffuseScatterMode::ApproxLambert => random_point_in_unit_sphere(rng),
+            DiffuseScatterMode::TrueLambert => {
+                random_point_in_unit_sphere(rng).unit_vector().unwrap()
+            }
+            DiffuseScatterMode::Hemispherical => {
+                random_point_in_hemisphere(rng, hit_record.normal())
+            }
         };
 
         let mut scatter_direction = *hit_record.normal() + diffuse_scatter_mode;
@@ -35,6 +49,10 @@ impl Material for DiffuseMaterial {
             scatter_direction = *hit_record.normal()
         }
 
-        Some((Ray::new(hit_record.point(), &scatter_direction), self.d_albedo))
+        let albedo = self
+            .d_albedo
+            .value(hit_record.u(), hit_record.v(), hit_record.point());
+
+        Some((Ray::new(hit_record.point(), &scatter_direction), albedo))
     }
 }
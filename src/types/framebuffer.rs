@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use super::color::{self, Color};
+
+/// In-memory image of linear pixel colors, stored row by row from the top of the image
+#[derive(Debug, Clone)]
+pub struct Framebuffer {
+    d_width: usize,
+    d_height: usize,
+    d_pixels: Vec<Color>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Framebuffer {
+            d_width: width,
+            d_height: height,
+            d_pixels: vec![Color::zero_vec(); width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.d_width
+    }
+
+    pub fn height(&self) -> usize {
+        self.d_height
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.d_pixels
+    }
+
+    /// Color at column x of row y, where row 0 is the top of the image
+    pub fn pixel(&self, x: usize, y: usize) -> &Color {
+        &self.d_pixels[y * self.d_width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: &Color) {
+        self.d_pixels[y * self.d_width + x] = *color;
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [Color] {
+        &mut self.d_pixels[y * self.d_width..(y + 1) * self.d_width]
+    }
+
+    /// Gamma corrected 8-bit RGB bytes for the whole image
+    pub fn to_rgb8(&self, gamma: f64) -> Vec<u8> {
+        self.d_pixels
+            .iter()
+            .flat_map(|pixel| color::to_rgb8(pixel, gamma))
+            .collect()
+    }
+}
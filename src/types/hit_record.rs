@@ -11,6 +11,8 @@ pub struct HitRecord {
     d_trace: f64,
     d_point: Point3,
     d_normal: Vec3,
+    d_u: f64,
+    d_v: f64,
     d_front_face: bool,
     d_material: Box<dyn Material>,
 }
@@ -20,15 +22,18 @@ impl HitRecord {
         trace: f64,
         point: Point3,
         normal: Vec3,
+        (u, v): (f64, f64),
         front_face: bool,
-        material: &Box<dyn Material>,
+        material: &dyn Material,
     ) -> Self {
         HitRecord {
             d_trace: trace,
             d_point: point,
             d_normal: normal,
+            d_u: u,
+            d_v: v,
             d_front_face: front_face,
-            d_material: material.clone(),
+            d_material: material.clone_box(),
         }
     }
 
@@ -53,11 +58,20 @@ impl HitRecord {
         &self.d_normal
     }
 
+    /// Surface texture coordinates of the hit point
+    pub fn u(&self) -> f64 {
+        self.d_u
+    }
+
+    pub fn v(&self) -> f64 {
+        self.d_v
+    }
+
     pub fn is_front_facing(&self) -> bool {
         self.d_front_face
     }
 
-    pub fn material(&self) -> &Box<dyn Material> {
-        &self.d_material
+    pub fn material(&self) -> &dyn Material {
+        self.d_material.as_ref()
     }
 }
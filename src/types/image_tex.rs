@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+use super::{color::Color, framebuffer::Framebuffer, hit_record::Point3, texture::Texture};
+
+/// Image mapped onto texture coordinates, repeating outside of [0, 1].
+/// v = 0 is the bottom row of the image
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    d_image: Framebuffer,
+}
+
+impl ImageTexture {
+    pub fn new(image: Framebuffer) -> Result<Self, &'static str> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err("Texture image must not be empty");
+        }
+
+        Ok(ImageTexture { d_image: image })
+    }
+
+    pub fn image(&self) -> &Framebuffer {
+        &self.d_image
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, _point: &Point3) -> Color {
+        let width = self.d_image.width();
+        let height = self.d_image.height();
+
+        let x = ((u.rem_euclid(1.0) * width as f64) as usize).min(width - 1);
+        let y = (((1.0 - v.rem_euclid(1.0)) * height as f64) as usize).min(height - 1);
+
+        *self.d_image.pixel(x, y)
+    }
+}
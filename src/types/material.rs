@@ -1,14 +1,26 @@
-use crate::{components::ray::Ray, utils::config::Config};
+use crate::{
+    components::ray::Ray,
+    utils::{config::Config, utilities::RandomGenerator},
+};
 
-use super::{color::Color, hit_record::HitRecord};
+use super::{
+    color::{Color, COLOR_BLACK},
+    hit_record::HitRecord,
+};
 
-pub trait Material: CloneableMaterial {
+pub trait Material: CloneableMaterial + Send + Sync {
     fn scatter(
         &self,
         parent_ray: &Ray,
         hit_record: &HitRecord,
         scene_config: &Config,
+        rng: &mut RandomGenerator,
     ) -> Option<(Ray, Color)>;
+
+    /// Radiance given off by the material at the hit point. Only lights emit
+    fn emitted(&self, _hit_record: &HitRecord) -> Color {
+        COLOR_BLACK
+    }
 }
 
 pub trait CloneableMaterial {
@@ -1,21 +1,28 @@
 #![allow(dead_code)]
 
+use std::sync::Arc;
+
+use crate::components::ray::Ray;
 use crate::utils::config::Config;
-use crate::utils::utilities::clamp;
-use crate::{components::ray::Ray, utils::utilities::random_point_in_unit_sphere};
+use crate::utils::utilities::{clamp, random_point_in_unit_sphere, RandomGenerator};
 
-use super::{color::Color, material::Material};
+use super::{color::Color, material::Material, solid_tex::SolidTexture, texture::Texture};
 
 #[derive(Clone)]
 pub struct MetalMaterial {
-    d_albedo: Color,
+    d_albedo: Arc<dyn Texture>,
     d_roughness: f64,
 }
 
 impl MetalMaterial {
     pub fn new(albedo: &Color, roughness: f64) -> Self {
+        MetalMaterial::from_texture(Arc::new(SolidTexture::new(albedo)), roughness)
+    }
+
+    /// Create a material whose reflected color varies over the surface
+    pub fn from_texture(albedo: Arc<dyn Texture>, roughness: f64) -> Self {
         MetalMaterial {
-            d_albedo: *albedo,
+            d_albedo: albedo,
             d_roughness: clamp(roughness, 0.0, 1.0),
         }
     }
@@ -27,6 +34,7 @@ impl Material for MetalMaterial {
         parent_ray: &crate::components::ray::Ray,
         hit_record: &super::hit_record::HitRecord,
         _scene_config: &Config,
+        rng: &mut RandomGenerator,
     ) -> Option<(Ray, Color)> {
         let reflected_direction = parent_ray
             .direction()
@@ -36,11 +44,14 @@ impl Material for MetalMaterial {
 
         let scattered_ray = Ray::new(
             hit_record.point(),
-            &(reflected_direction + random_point_in_unit_sphere().scaled(self.d_roughness)),
+            &(reflected_direction + random_point_in_unit_sphere(rng).scaled(self.d_roughness)),
         );
 
         if reflected_direction.dot(hit_record.normal()) > 0.0 {
-            Some((scattered_ray, self.d_albedo))
+            let albedo = self
+                .d_albedo
+                .value(hit_record.u(), hit_record.v(), hit_record.point());
+            Some((scattered_ray, albedo))
         } else {
             None
         }
@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+
+use crate::utils::perlin::Perlin;
+
+use super::{color::Color, hit_record::Point3, texture::Texture};
+
+/// Pattern a noise texture derives from Perlin noise
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+pub enum NoiseStyle {
+    /// Plain smooth noise
+    Smooth,
+    /// Wispy turbulence
+    Cloud,
+    /// Veins along the Z axis, distorted by turbulence
+    Marble,
+    /// Rings around the Y axis, distorted by noise
+    Wood,
+}
+
+/// Procedural texture blending between two colors by a noise pattern
+#[derive(Debug, Clone)]
+pub struct NoiseTexture {
+    d_noise: Perlin,
+    d_style: NoiseStyle,
+    d_scale: f64,
+    d_octaves: u32,
+    d_low: Color,
+    d_high: Color,
+}
+
+impl NoiseTexture {
+    /// Create a texture whose pattern repeats about every 1 / scale world units
+    pub fn new(
+        style: NoiseStyle,
+        scale: f64,
+        octaves: u32,
+        seed: u64,
+        low: &Color,
+        high: &Color,
+    ) -> Self {
+        NoiseTexture {
+            d_noise: Perlin::new(seed),
+            d_style: style,
+            d_scale: scale,
+            d_octaves: octaves,
+            d_low: *low,
+            d_high: *high,
+        }
+    }
+
+    /// Pattern intensity in [0, 1] at a point
+    pub fn intensity(&self, point: &Point3) -> f64 {
+        let scaled_point = point.scaled(self.d_scale);
+
+        let intensity = match self.d_style {
+            NoiseStyle::Smooth => 0.5 * (1.0 + self.d_noise.noise(&scaled_point)),
+            NoiseStyle::Cloud => self.d_noise.turbulence(&scaled_point, self.d_octaves),
+            NoiseStyle::Marble => {
+                let turbulence = self.d_noise.turbulence(&scaled_point, self.d_octaves);
+                0.5 * (1.0 + (scaled_point.z() + 10.0 * turbulence).sin())
+            }
+            NoiseStyle::Wood => {
+                let radius = (scaled_point.x().powi(2) + scaled_point.z().powi(2)).sqrt();
+                (4.0 * radius + 2.0 * self.d_noise.noise(&scaled_point)).rem_euclid(1.0)
+            }
+        };
+
+        intensity.clamp(0.0, 1.0)
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, _u: f64, _v: f64, point: &Point3) -> Color {
+        let intensity = self.intensity(point);
+        self.d_low.scaled(1.0 - intensity) + self.d_high.scaled(intensity)
+    }
+}
@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+
+use super::{color::Color, hit_record::Point3, texture::Texture};
+
+/// Texture with the same color everywhere
+#[derive(Debug, Clone)]
+pub struct SolidTexture {
+    d_color: Color,
+}
+
+impl SolidTexture {
+    pub fn new(color: &Color) -> Self {
+        SolidTexture { d_color: *color }
+    }
+}
+
+impl Texture for SolidTexture {
+    fn value(&self, _u: f64, _v: f64, _point: &Point3) -> Color {
+        self.d_color
+    }
+}
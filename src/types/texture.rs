@@ -0,0 +1,8 @@
+#![allow(dead_code)]
+
+use super::{color::Color, hit_record::Point3};
+
+/// Color of a surface, looked up by texture coordinates or position of the hit point
+pub trait Texture: Send + Sync {
+    fn value(&self, u: f64, v: f64, point: &Point3) -> Color;
+}
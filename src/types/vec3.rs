@@ -5,9 +5,11 @@ use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 use serde::{Deserialize, Serialize};
 
-use crate::utils::utilities::{fmin, random_f64, random_f64_between};
+use crate::utils::utilities::{fmin, random_f64, random_f64_between, RandomGenerator};
 
+/// Serialized as an array of its coordinates, e.g. `look_from = [0.0, 1.0, -2.0]`
 #[derive(PartialEq, PartialOrd, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "[f64; 3]", into = "[f64; 3]")]
 pub struct Vec3 {
     d_x: f64,
     d_y: f64,
@@ -33,19 +35,23 @@ impl Vec3 {
         }
     }
 
-    pub fn random() -> Self {
+    pub fn random(rng: &mut RandomGenerator) -> Self {
         Vec3 {
-            d_x: random_f64(),
-            d_y: random_f64(),
-            d_z: random_f64(),
+            d_x: random_f64(rng),
+            d_y: random_f64(rng),
+            d_z: random_f64(rng),
         }
     }
 
-    pub fn random_between(min_inclusive: f64, max_exclusive: f64) -> Self {
+    pub fn random_between(
+        rng: &mut RandomGenerator,
+        min_inclusive: f64,
+        max_exclusive: f64,
+    ) -> Self {
         Vec3 {
-            d_x: random_f64_between(min_inclusive, max_exclusive),
-            d_y: random_f64_between(min_inclusive, max_exclusive),
-            d_z: random_f64_between(min_inclusive, max_exclusive),
+            d_x: random_f64_between(rng, min_inclusive, max_exclusive),
+            d_y: random_f64_between(rng, min_inclusive, max_exclusive),
+            d_z: random_f64_between(rng, min_inclusive, max_exclusive),
         }
     }
 
@@ -130,10 +136,10 @@ impl Vec3 {
     }
 
     pub fn refract(&self, normal: &Vec3, relative_ior: f64) -> Self {
-        let cos_theta = fmin(self.dot(normal) * -1.0, 1.0);
+        let cos_theta = fmin(-self.dot(normal), 1.0);
         let ray_out_orth = (*self + normal.scaled(cos_theta)).scaled(relative_ior);
         let ray_out_prll = normal.scaled(-(1.0 - ray_out_orth.len_squared()).abs().sqrt());
-        return ray_out_orth + ray_out_prll;
+        ray_out_orth + ray_out_prll
     }
 
     pub fn is_nearly_zero(&self) -> bool {
@@ -164,6 +170,18 @@ impl Vec3 {
     }
 }
 
+impl From<[f64; 3]> for Vec3 {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl From<Vec3> for [f64; 3] {
+    fn from(v: Vec3) -> Self {
+        [v.d_x, v.d_y, v.d_z]
+    }
+}
+
 /// Implement unary minus (-v) operation for Vec3
 impl Neg for Vec3 {
     type Output = Self;
@@ -1,2 +1,11 @@
+pub mod cli;
 pub mod config;
+pub mod config_builder;
+pub mod hdr_loader;
+pub mod image_reader;
+pub mod image_writer;
+pub mod obj_loader;
+pub mod perlin;
+pub mod render_stats;
+pub mod scene;
 pub mod utilities;
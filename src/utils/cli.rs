@@ -0,0 +1,240 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::config::Config;
+
+pub const USAGE: &str = "\
+Usage: raytracer-rs [OPTIONS]
+
+Options:
+  -c, --config <FILE>    Render settings [default: settings.toml]
+  -s, --scene <FILE>     Scene description [default: scene.toml]
+  -o, --output <FILE>    Output image, PNG unless the extension is .ppm
+  -p, --preset <NAME>    Quality preset: draft, preview, final or one defined
+                         in the settings
+      --width <PIXELS>   Image width
+      --height <PIXELS>  Image height
+      --samples <COUNT>  Samples per pixel, or the maximum with adaptive sampling
+      --seed <SEED>      Seed for random sampling
+      --threads <COUNT>  Number of render threads, 0 for all cores
+      --dump-config      Print the resolved settings as TOML and exit
+      --strict           Refuse to render if any settings cannot be loaded,
+                         instead of ignoring them
+  -h, --help             Print this help
+
+Settings are read from the settings file, then the [settings] table of the scene,
+then RAYTRACER_<SECTION>_<OPTION> environment variables such as
+RAYTRACER_IMAGE_WIDTH=800, then the options above, each taking precedence
+over the ones before. A preset selected with --preset or RAYTRACER_PRESET
+overrides the settings file and the scene settings, and scales the image size
+they set. The environment variables and the other options above override it.";
+
+/// Errors raised while parsing the command line
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    UnknownArgument { argument: String },
+    MissingValue { option: String },
+    InvalidValue { option: String, value: String },
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::UnknownArgument { argument } => {
+                write!(f, "Unknown argument '{}'", argument)
+            }
+            CliError::MissingValue { option } => write!(f, "Missing value for {}", option),
+            CliError::InvalidValue { option, value } => {
+                write!(f, "Invalid value '{}' for {}", value, option)
+            }
+        }
+    }
+}
+
+/// Arguments of the render binary
+#[derive(Debug, PartialEq)]
+pub struct CliArgs {
+    d_config_path: String,
+    d_scene_path: String,
+    d_output_path: Option<String>,
+    d_preset: Option<String>,
+    d_width: Option<u64>,
+    d_height: Option<u64>,
+    d_samples: Option<u64>,
+    d_seed: Option<u64>,
+    d_threads: Option<usize>,
+    d_strict: bool,
+    d_dump_config: bool,
+    d_help: bool,
+}
+
+impl CliArgs {
+    /********* Initializers *********/
+
+    /// Parse the arguments following the program name. Options take their value either
+    /// as the next argument or after an equals sign, e.g. `--width=800`
+    pub fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<Self, CliError> {
+        let mut cli_args = CliArgs {
+            d_config_path: "settings.toml".to_string(),
+            d_scene_path: "scene.toml".to_string(),
+            d_output_path: None,
+            d_preset: None,
+            d_width: None,
+            d_height: None,
+            d_samples: None,
+            d_seed: None,
+            d_threads: None,
+            d_strict: false,
+            d_dump_config: false,
+            d_help: false,
+        };
+
+        let mut arguments = arguments.into_iter();
+        while let Some(argument) = arguments.next() {
+            let (option, inline_value) = match argument.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => {
+                    (option.to_string(), Some(value.to_string()))
+                }
+                _ => (argument.clone(), None),
+            };
+
+            if option == "-h" || option == "--help" {
+                cli_args.d_help = true;
+                continue;
+            }
+            if option == "--strict" {
+                cli_args.d_strict = true;
+                continue;
+            }
+            if option == "--dump-config" {
+                cli_args.d_dump_config = true;
+                continue;
+            }
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| arguments.next())
+                    .ok_or_else(|| CliError::MissingValue {
+                        option: option.clone(),
+                    })
+            };
+
+            match option.as_str() {
+                "-c" | "--config" => cli_args.d_config_path = value()?,
+                "-s" | "--scene" => cli_args.d_scene_path = value()?,
+                "-o" | "--output" => cli_args.d_output_path = Some(value()?),
+                "-p" | "--preset" => cli_args.d_preset = Some(value()?),
+                "--width" => cli_args.d_width = Some(parse_value(&option, value()?)?),
+                "--height" => cli_args.d_height = Some(parse_value(&option, value()?)?),
+                "--samples" => cli_args.d_samples = Some(parse_value(&option, value()?)?),
+                "--seed" => cli_args.d_seed = Some(parse_value(&option, value()?)?),
+                "--threads" => cli_args.d_threads = Some(parse_value(&option, value()?)?),
+                _ => return Err(CliError::UnknownArgument { argument }),
+            }
+        }
+
+        for (option, dimension) in [
+            ("--width", cli_args.d_width),
+            ("--height", cli_args.d_height),
+        ] {
+            if dimension == Some(0) {
+                return Err(CliError::InvalidValue {
+                    option: option.to_string(),
+                    value: "0".to_string(),
+                });
+            }
+        }
+
+        Ok(cli_args)
+    }
+
+    /************ Getters ************/
+
+    pub fn config_path(&self) -> &str {
+        &self.d_config_path
+    }
+
+    pub fn scene_path(&self) -> &str {
+        &self.d_scene_path
+    }
+
+    pub fn output_path(&self) -> Option<&str> {
+        self.d_output_path.as_deref()
+    }
+
+    /// Preset overriding the settings file and the scene settings
+    pub fn preset(&self) -> Option<&str> {
+        self.d_preset.as_deref()
+    }
+
+    pub fn width(&self) -> Option<u64> {
+        self.d_width
+    }
+
+    pub fn height(&self) -> Option<u64> {
+        self.d_height
+    }
+
+    pub fn samples(&self) -> Option<u64> {
+        self.d_samples
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.d_seed
+    }
+
+    pub fn threads(&self) -> Option<usize> {
+        self.d_threads
+    }
+
+    /// Whether config errors stop the render instead of falling back to defaults
+    pub fn strict(&self) -> bool {
+        self.d_strict
+    }
+
+    /// Whether to print the resolved config instead of rendering
+    pub fn dump_config(&self) -> bool {
+        self.d_dump_config
+    }
+
+    pub fn help(&self) -> bool {
+        self.d_help
+    }
+
+    /************ Overrides ************/
+
+    /// Replace the settings of the config that were given on the command line
+    pub fn apply(&self, config: &mut Config) {
+        let image_config = config.image_config_mut();
+        if let Some(width) = self.d_width {
+            image_config.set_image_width(width);
+        }
+        if let Some(height) = self.d_height {
+            image_config.set_image_height(height);
+        }
+        if let Some(samples) = self.d_samples {
+            image_config.set_samples_per_pixel(samples);
+        }
+        if let Some(output_path) = &self.d_output_path {
+            image_config.set_output_path(output_path);
+        }
+
+        let render_config = config.render_config_mut();
+        if let Some(seed) = self.d_seed {
+            render_config.set_seed(seed);
+        }
+        if let Some(threads) = self.d_threads {
+            render_config.set_threads(threads);
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(option: &str, value: String) -> Result<T, CliError> {
+    value.parse().map_err(|_| CliError::InvalidValue {
+        option: option.to_string(),
+        value,
+    })
+}
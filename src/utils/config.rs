@@ -1,24 +1,156 @@
 #![allow(dead_code)]
 
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use serde::de::{value::MapAccessDeserializer, Deserializer, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
 
-use crate::types::vec3::Vec3;
+use super::config_builder::ConfigBuilder;
+use crate::types::{
+    color::{Color, COLOR_WHITE},
+    vec3::Vec3,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MsaaConfig {
-    samples_per_pixel: u64,
+// Samples per pixel for MSAA when the count is not configured
+const DEFAULT_MSAA_SAMPLES: u64 = 50;
+// Sample bounds and noise threshold for adaptive sampling when not configured
+const DEFAULT_ADAPTIVE_MIN_SAMPLES: u64 = 16;
+const DEFAULT_ADAPTIVE_MAX_SAMPLES: u64 = 256;
+const DEFAULT_ADAPTIVE_THRESHOLD: f64 = 0.02;
+// Bounces before Russian roulette may stop a path when not configured
+const DEFAULT_ROULETTE_MIN_DEPTH: u64 = 5;
+// Samples per pixel added by each progressive pass when not configured
+const DEFAULT_PASS_SAMPLES: u64 = 8;
+
+/// Errors raised while loading the render configuration
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: String,
+        error: std::io::Error,
+    },
+    /// Malformed TOML, unknown options or values of the wrong type, with the zero-based
+    /// line and column
+    Parse {
+        path: Option<String>,
+        message: String,
+        position: Option<(usize, usize)>,
+    },
+    Validation {
+        option: String,
+        reason: String,
+    },
 }
 
-impl MsaaConfig {
-    pub fn samples_per_pixel(&self) -> u64 {
-        self.samples_per_pixel
+impl ConfigError {
+    /// Attach the path of the file a parse error was found in
+    pub fn in_file(self, path: &str) -> Self {
+        match self {
+            ConfigError::Parse {
+                path: None,
+                message,
+                position,
+            } => ConfigError::Parse {
+                path: Some(path.to_string()),
+                message,
+                position,
+            },
+            config_error => config_error,
+        }
     }
 }
 
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => {
+                write!(f, "Unable to read config file '{}': {}", path, error)
+            }
+            ConfigError::Parse {
+                path,
+                message,
+                position,
+            } => {
+                write!(f, "Unable to parse config")?;
+                if let Some(path) = path {
+                    write!(f, " file '{}'", path)?;
+                }
+                if let Some((line, column)) = position {
+                    write!(f, " at line {}, column {}", line + 1, column + 1)?;
+                }
+                // The TOML message repeats the position at its end
+                let message = message.split(" at line ").next().unwrap_or(message);
+                write!(f, ": {}", message)
+            }
+            ConfigError::Validation { option, reason } => {
+                write!(f, "Invalid config option {}: {}", option, reason)
+            }
+        }
+    }
+}
+
+/// Antialiasing mode with its parameters, e.g. `aa = { mode = "MSAA", samples = 200 }`
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", deny_unknown_fields)]
 pub enum AntialiasingMode {
-    None,
-    MSAA, // TODO: Figure out how to deserialize typed Enum variants in TOML
+    /// One sample per pixel. Written with braces, as the options given to unit
+    /// variants would be ignored rather than rejected
+    None {},
+    /// A number of samples placed by the configured sampler
+    MSAA { samples: Option<u64> },
+    /// Supersampling on a regular size x size grid
+    Grid { size: u64 },
+    /// Between min_samples and max_samples placed by the configured sampler, stopping
+    /// once the relative noise of the pixel is below the threshold. The number of
+    /// samples taken per pixel can be written to a debug image
+    Adaptive {
+        min_samples: Option<u64>,
+        max_samples: Option<u64>,
+        threshold: Option<f64>,
+        sample_count_path: Option<String>,
+    },
+}
+
+/// Read an antialiasing mode from its table, or from the mode name alone as written by
+/// the former `aa_mode = "MSAA"` option
+fn deserialize_aa<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<AntialiasingMode>, D::Error> {
+    struct AntialiasingModeVisitor;
+
+    impl<'de> Visitor<'de> for AntialiasingModeVisitor {
+        type Value = AntialiasingMode;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "an antialiasing mode table or name")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Self::Value, E> {
+            match name {
+                "None" => Ok(AntialiasingMode::None {}),
+                "MSAA" => Ok(AntialiasingMode::MSAA { samples: None }),
+                _ => Err(E::unknown_variant(name, &["None", "MSAA"])),
+            }
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            AntialiasingMode::deserialize(MapAccessDeserializer::new(map))
+        }
+    }
+
+    deserializer
+        .deserialize_any(AntialiasingModeVisitor)
+        .map(Some)
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SamplerMode {
+    Independent,
+    Stratified,
+    Halton,
+    Sobol,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,12 +160,49 @@ pub enum DiffuseScatterMode {
     Hemispherical,
 }
 
+/// Algorithm following the paths of light through the scene
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum IntegratorMode {
+    Recursive,
+    Iterative,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum AccelerationStructure {
+    Linear,
+    BVH,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum ImageFormat {
+    PNG,
+    PPM,
+}
+
+impl ImageFormat {
+    /// Format matching the extension of an image path, PNG unless it is `.ppm`
+    pub fn from_path(path: &str) -> &'static ImageFormat {
+        if path.to_lowercase().ends_with(".ppm") {
+            &ImageFormat::PPM
+        } else {
+            &ImageFormat::PNG
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ImageConfig {
     image_width: u64,
     image_height: u64,
     gamma: Option<f64>,
-    aa_mode: Option<AntialiasingMode>,
+    #[serde(alias = "aa_mode", deserialize_with = "deserialize_aa")]
+    aa: Option<AntialiasingMode>,
+    sampler: Option<SamplerMode>,
+    output_path: Option<String>,
+    output_format: Option<ImageFormat>,
 }
 
 impl ImageConfig {
@@ -45,53 +214,168 @@ impl ImageConfig {
         self.image_height
     }
 
+    pub fn aspect_ratio(&self) -> f64 {
+        self.image_width as f64 / self.image_height as f64
+    }
+
     pub fn gamma(&self) -> f64 {
         self.gamma.unwrap_or(1.0)
     }
 
     pub fn aa_mode(&self) -> &AntialiasingMode {
-        self.aa_mode.as_ref().unwrap_or(&AntialiasingMode::None)
+        self.aa.as_ref().unwrap_or(&AntialiasingMode::None {})
     }
 
+    pub fn output_path(&self) -> &str {
+        self.output_path.as_deref().unwrap_or("render.png")
+    }
+
+    /// Output file format, inferred from the output path extension if not set
+    pub fn output_format(&self) -> &ImageFormat {
+        self.output_format
+            .as_ref()
+            .unwrap_or_else(|| ImageFormat::from_path(self.output_path()))
+    }
+
+    /// Number of samples per pixel, or the maximum number with adaptive sampling
     pub fn samples_per_pixel(&self) -> u64 {
-        if let AntialiasingMode::MSAA = self.aa_mode() {
-            return 50;
-        } else {
-            return 1;
+        match self.aa_mode() {
+            AntialiasingMode::None {} => 1,
+            AntialiasingMode::MSAA { samples } => samples.unwrap_or(DEFAULT_MSAA_SAMPLES).max(1),
+            AntialiasingMode::Grid { size } => size.max(&1).pow(2),
+            AntialiasingMode::Adaptive { max_samples, .. } => max_samples
+                .unwrap_or(DEFAULT_ADAPTIVE_MAX_SAMPLES)
+                .max(self.min_samples_per_pixel()),
         }
     }
+
+    /// Number of samples every pixel takes, even if it converges earlier
+    pub fn min_samples_per_pixel(&self) -> u64 {
+        match self.aa_mode() {
+            AntialiasingMode::Adaptive { min_samples, .. } => {
+                min_samples.unwrap_or(DEFAULT_ADAPTIVE_MIN_SAMPLES).max(2)
+            }
+            _ => self.samples_per_pixel(),
+        }
+    }
+
+    /// Relative noise below which adaptive sampling stops, None for a fixed sample count
+    pub fn noise_threshold(&self) -> Option<f64> {
+        match self.aa_mode() {
+            AntialiasingMode::Adaptive { threshold, .. } => {
+                Some(threshold.unwrap_or(DEFAULT_ADAPTIVE_THRESHOLD))
+            }
+            _ => None,
+        }
+    }
+
+    /// Path of the debug image of per-pixel sample counts, if one should be written
+    pub fn sample_count_path(&self) -> Option<&str> {
+        match self.aa_mode() {
+            AntialiasingMode::Adaptive {
+                sample_count_path, ..
+            } => sample_count_path.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn sampler(&self) -> &SamplerMode {
+        self.sampler.as_ref().unwrap()
+    }
+
+    /************ Setters ************/
+
+    pub fn set_sampler(&mut self, sampler: SamplerMode) {
+        self.sampler = Some(sampler);
+    }
+
+    pub fn set_image_width(&mut self, image_width: u64) {
+        self.image_width = image_width;
+    }
+
+    pub fn set_image_height(&mut self, image_height: u64) {
+        self.image_height = image_height;
+    }
+
+    /// Set the number of samples per pixel of the antialiasing mode. This is the
+    /// maximum for adaptive sampling, the nearest square for grid supersampling, and
+    /// switches from no antialiasing to MSAA
+    pub fn set_samples_per_pixel(&mut self, samples: u64) {
+        let samples = samples.max(1);
+        self.aa = Some(match self.aa.take() {
+            Some(AntialiasingMode::Grid { .. }) => AntialiasingMode::Grid {
+                size: ((samples as f64).sqrt().round() as u64).max(1),
+            },
+            Some(AntialiasingMode::Adaptive {
+                min_samples,
+                threshold,
+                sample_count_path,
+                ..
+            }) => AntialiasingMode::Adaptive {
+                min_samples: min_samples.map(|min_samples| min_samples.min(samples)),
+                max_samples: Some(samples),
+                threshold,
+                sample_count_path,
+            },
+            _ => AntialiasingMode::MSAA {
+                samples: Some(samples),
+            },
+        });
+    }
+
+    /// Set the output image path, the format then being inferred from its extension
+    pub fn set_output_path(&mut self, output_path: &str) {
+        self.output_path = Some(output_path.to_string());
+        self.output_format = None;
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CameraConfig {
-    viewport_width: u64,
-    viewport_height: u64,
-    origin: Option<Vec3>,
-    focal_length: Option<f64>,
+    look_from: Option<Vec3>,
+    look_at: Option<Vec3>,
+    vup: Option<Vec3>,
+    vertical_fov: Option<f64>,
+    aperture: Option<f64>,
+    focus_distance: Option<f64>,
 }
 
 impl CameraConfig {
-    pub fn viewport_width(&self) -> u64 {
-        self.viewport_width
+    pub fn look_from(&self) -> &Vec3 {
+        self.look_from.as_ref().unwrap()
+    }
+
+    pub fn look_at(&self) -> &Vec3 {
+        self.look_at.as_ref().unwrap()
+    }
+
+    pub fn vup(&self) -> &Vec3 {
+        self.vup.as_ref().unwrap()
     }
 
-    pub fn viewport_height(&self) -> u64 {
-        self.viewport_height
+    pub fn vertical_fov(&self) -> f64 {
+        self.vertical_fov.unwrap()
     }
 
-    pub fn origin(&self) -> &Vec3 {
-        self.origin.as_ref().unwrap()
+    pub fn aperture(&self) -> f64 {
+        self.aperture.unwrap()
     }
 
-    pub fn focal_length(&self) -> f64 {
-        self.focal_length.unwrap()
+    /// Distance to the plane in perfect focus, which defaults to the look_at point
+    pub fn focus_distance(&self) -> f64 {
+        self.focus_distance
+            .unwrap_or_else(|| (*self.look_from() - *self.look_at()).len())
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RaysConfig {
     max_child_rays: Option<u64>,
     diffuse_scatter_mode: Option<DiffuseScatterMode>,
+    russian_roulette: Option<bool>,
+    roulette_min_depth: Option<u64>,
 }
 
 impl RaysConfig {
@@ -104,66 +388,394 @@ impl RaysConfig {
             .as_ref()
             .unwrap_or(&DiffuseScatterMode::TrueLambert)
     }
+
+    /// Whether paths randomly stop once they contribute little to the image
+    pub fn russian_roulette(&self) -> bool {
+        self.russian_roulette.unwrap_or(false)
+    }
+
+    /// Number of bounces every path takes before Russian roulette may stop it
+    pub fn roulette_min_depth(&self) -> u64 {
+        self.roulette_min_depth
+            .unwrap_or(DEFAULT_ROULETTE_MIN_DEPTH)
+    }
+
+    pub fn set_max_child_rays(&mut self, max_child_rays: u64) {
+        self.max_child_rays = Some(max_child_rays);
+    }
+}
+
+/// Named bundle of quality options, e.g.
+/// `[presets.overnight] resolution_scale = 2.0, samples = 4096`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PresetConfig {
+    resolution_scale: Option<f64>,
+    samples: Option<u64>,
+    max_depth: Option<u64>,
+    sampler: Option<SamplerMode>,
+}
+
+impl PresetConfig {
+    pub const fn new(
+        resolution_scale: Option<f64>,
+        samples: Option<u64>,
+        max_depth: Option<u64>,
+        sampler: Option<SamplerMode>,
+    ) -> Self {
+        PresetConfig {
+            resolution_scale,
+            samples,
+            max_depth,
+            sampler,
+        }
+    }
+
+    /// Built-in preset of the given name: draft, preview or final
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "draft" => Some(PresetConfig::new(
+                Some(0.25),
+                Some(4),
+                Some(8),
+                Some(SamplerMode::Independent),
+            )),
+            "preview" => Some(PresetConfig::new(
+                Some(0.5),
+                Some(32),
+                Some(16),
+                Some(SamplerMode::Stratified),
+            )),
+            "final" => {
+                Some(PresetConfig::new(Some(1.0), Some(512), Some(50), Some(SamplerMode::Sobol)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Factor applied to the configured image width and height
+    pub fn resolution_scale(&self) -> Option<f64> {
+        self.resolution_scale
+    }
+
+    /// Samples per pixel, or the maximum with adaptive sampling
+    pub fn samples(&self) -> Option<u64> {
+        self.samples
+    }
+
+    /// Maximum number of times a ray scatters
+    pub fn max_depth(&self) -> Option<u64> {
+        self.max_depth
+    }
+
+    pub fn sampler(&self) -> Option<&SamplerMode> {
+        self.sampler.as_ref()
+    }
 }
 
+/// Radiance of rays that miss every object
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum BackgroundConfig {
+    Black {},
+    Solid {
+        color: Color,
+    },
+    Gradient {
+        bottom: Color,
+        top: Color,
+    },
+    EnvironmentMap {
+        file: String,
+        rotation: Option<f64>,
+        intensity: Option<f64>,
+    },
+}
+
+/// Rendering the image in passes, saving the progress after each pass
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgressiveConfig {
+    pass_samples: Option<u64>,
+    accumulation_path: Option<String>,
+}
+
+impl ProgressiveConfig {
+    /// Samples per pixel added to the image by each pass, at least 1
+    pub fn pass_samples(&self) -> u64 {
+        self.pass_samples.unwrap_or(DEFAULT_PASS_SAMPLES).max(1)
+    }
+
+    /// File the accumulated samples are saved to after each pass and resumed from
+    pub fn accumulation_path(&self) -> Option<&str> {
+        self.accumulation_path.as_deref()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenderConfig {
+    threads: Option<usize>,
+    seed: Option<u64>,
+    acceleration: Option<AccelerationStructure>,
+    integrator: Option<IntegratorMode>,
+    progressive: Option<ProgressiveConfig>,
+    progress: Option<bool>,
+}
+
+impl RenderConfig {
+    /// Number of render threads, using all available cores if unset or 0
+    pub fn threads(&self) -> usize {
+        match self.threads {
+            Some(threads) if threads > 0 => threads,
+            _ => std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+        }
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Set the number of render threads, 0 using all available cores
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = Some(threads);
+    }
+
+    pub fn acceleration(&self) -> &AccelerationStructure {
+        self.acceleration
+            .as_ref()
+            .unwrap_or(&AccelerationStructure::BVH)
+    }
+
+    pub fn integrator(&self) -> &IntegratorMode {
+        self.integrator
+            .as_ref()
+            .unwrap_or(&IntegratorMode::Recursive)
+    }
+
+    /// Whether to report the progress of each pass on stderr
+    pub fn show_progress(&self) -> bool {
+        self.progress.unwrap_or(true)
+    }
+
+    /// Progressive rendering settings, if the image is rendered in passes
+    pub fn progressive(&self) -> Option<&ProgressiveConfig> {
+        self.progressive.as_ref()
+    }
+}
+
+/// Render settings. Options missing from a config file take their default values
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     image: ImageConfig,
     camera: CameraConfig,
     rays: RaysConfig,
+    render: RenderConfig,
+    background: Option<BackgroundConfig>,
+    preset: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    presets: BTreeMap<String, PresetConfig>,
 }
 
 impl Config {
     /********* Initializers *********/
 
-    pub fn default() -> Self {
-        DEFAULT_CONFIG_OBJECT
+    /// Load the config file, falling back to the default configuration with a
+    /// message if it cannot be loaded
+    pub fn from_toml(config_toml_file: &str) -> Self {
+        Config::load(config_toml_file).unwrap_or_else(|config_error| {
+            eprintln!("{}. Using default configuration", config_error);
+            DEFAULT_CONFIG_OBJECT
+        })
     }
 
-    pub fn from_toml(config_toml_file: &str) -> Self {
-        if let Ok(toml_content) = std::fs::read_to_string(config_toml_file) {
-            let mut config_object =
-                toml::from_str(toml_content.as_str()).unwrap_or_else(|toml_error| {
-                    eprintln!(
-                        "Unable to load config file: {}. Using default configuration",
-                        toml_error.to_string()
-                    );
-                    DEFAULT_CONFIG_OBJECT
-                });
-
-            if config_object.image.aa_mode.is_none() {
-                config_object.image.aa_mode = DEFAULT_CONFIG_OBJECT.image.aa_mode;
-            }
+    /// Parse a config, falling back to the default configuration with a message if it
+    /// is invalid
+    pub fn from_toml_str(toml_content: &str) -> Self {
+        Config::parse(toml_content).unwrap_or_else(|config_error| {
+            eprintln!("{}. Using default configuration", config_error);
+            DEFAULT_CONFIG_OBJECT
+        })
+    }
 
-            if config_object.image.gamma.is_none() {
-                config_object.image.gamma = DEFAULT_CONFIG_OBJECT.image.gamma;
-            }
+    /// Load and validate the config file
+    pub fn load(config_toml_file: &str) -> Result<Self, ConfigError> {
+        let toml_content =
+            std::fs::read_to_string(config_toml_file).map_err(|error| ConfigError::Io {
+                path: config_toml_file.to_string(),
+                error,
+            })?;
 
-            if config_object.camera.origin.is_none() {
-                config_object.camera.origin = DEFAULT_CONFIG_OBJECT.camera.origin;
-            }
+        Config::parse(toml_content.as_str())
+            .map_err(|config_error| config_error.in_file(config_toml_file))
+    }
 
-            if config_object.camera.focal_length.is_none() {
-                config_object.camera.focal_length = DEFAULT_CONFIG_OBJECT.camera.focal_length;
-            }
+    /// Parse and validate a config, unset options taking their default values
+    pub fn parse(toml_content: &str) -> Result<Self, ConfigError> {
+        let mut builder = ConfigBuilder::new();
+        builder.add_toml_str(toml_content)?;
+        builder.build()
+    }
 
-            if config_object.rays.max_child_rays.is_none() {
-                config_object.rays.max_child_rays = DEFAULT_CONFIG_OBJECT.rays.max_child_rays;
+    /// Scale the image by the resolution of the selected preset and check the
+    /// resulting options
+    pub fn resolve(mut self) -> Result<Self, ConfigError> {
+        if let Some(name) = self.preset.take() {
+            if let Some(scale) = self.find_preset(&name)?.resolution_scale() {
+                if scale <= 0.0 {
+                    return Err(ConfigError::Validation {
+                        option: format!("presets.{}.resolution_scale", name),
+                        reason: "must be positive".to_string(),
+                    });
+                }
+                let scaled = |size: u64| ((size as f64 * scale).round() as u64).max(1);
+                self.image.image_width = scaled(self.image.image_width);
+                self.image.image_height = scaled(self.image.image_height);
             }
+        }
+
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Preset defined in the config or, failing that, the built-in preset of the name
+    pub fn find_preset(&self, name: &str) -> Result<PresetConfig, ConfigError> {
+        self.presets
+            .get(name)
+            .cloned()
+            .or_else(|| PresetConfig::builtin(name))
+            .ok_or_else(|| ConfigError::Validation {
+                option: "preset".to_string(),
+                reason: format!("unknown preset '{}'", name),
+            })
+    }
+
+    /// Set the samples per pixel, max_child_rays and sampler of a preset. Its
+    /// resolution scale is applied by `resolve`
+    pub fn apply_preset(&mut self, preset: &PresetConfig) {
+        if let Some(samples) = preset.samples() {
+            self.image.set_samples_per_pixel(samples);
+        }
+        if let Some(max_depth) = preset.max_depth() {
+            self.rays.set_max_child_rays(max_depth);
+        }
+        if let Some(sampler) = preset.sampler() {
+            self.image.set_sampler(*sampler);
+        }
+    }
 
-            if config_object.rays.diffuse_scatter_mode.is_none() {
-                config_object.rays.diffuse_scatter_mode =
-                    DEFAULT_CONFIG_OBJECT.rays.diffuse_scatter_mode;
+    /// The config with every option resolved, in the format of the config file
+    pub fn to_toml_string(&self) -> String {
+        let options = toml::Value::try_from(self).expect("The config is representable in TOML");
+        toml::to_string(&options).expect("TOML values can be written")
+    }
+
+    /// Check that the options describe an image that can be rendered
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |option: &str, reason: &str| {
+            Err(ConfigError::Validation {
+                option: option.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+
+        let image = &self.image;
+        if image.image_width == 0 {
+            return invalid("image.image_width", "must be greater than 0");
+        }
+        if image.image_height == 0 {
+            return invalid("image.image_height", "must be greater than 0");
+        }
+        if image.gamma() <= 0.0 {
+            return invalid("image.gamma", "must be positive");
+        }
+
+        match image.aa_mode() {
+            AntialiasingMode::MSAA { samples: Some(0) } => {
+                return invalid("image.aa.samples", "must be greater than 0");
             }
+            AntialiasingMode::Grid { size: 0 } => {
+                return invalid("image.aa.size", "must be greater than 0");
+            }
+            AntialiasingMode::Adaptive {
+                min_samples,
+                max_samples,
+                threshold,
+                ..
+            } => {
+                if threshold.is_some_and(|threshold| threshold <= 0.0) {
+                    return invalid("image.aa.threshold", "must be positive");
+                }
+                if let (Some(min_samples), Some(max_samples)) = (min_samples, max_samples) {
+                    if min_samples > max_samples {
+                        return invalid("image.aa.min_samples", "must not exceed max_samples");
+                    }
+                }
+            }
+            _ => {}
+        }
 
-            return config_object;
+        let vertical_fov = self.camera.vertical_fov();
+        if vertical_fov <= 0.0 || vertical_fov >= 180.0 {
+            return invalid("camera.vertical_fov", "must be between 0 and 180 degrees");
+        }
+        if self.camera.aperture() < 0.0 {
+            return invalid("camera.aperture", "must not be negative");
+        }
+        if self
+            .camera
+            .focus_distance
+            .is_some_and(|distance| distance <= 0.0)
+        {
+            return invalid("camera.focus_distance", "must be positive");
+        }
+        let view_direction = *self.camera.look_at() - *self.camera.look_from();
+        if view_direction.len() == 0.0 {
+            return invalid("camera.look_at", "must differ from look_from");
+        }
+        if self.camera.vup().cross(&view_direction).len() == 0.0 {
+            return invalid("camera.vup", "must not be parallel to the viewing direction");
         }
 
-        DEFAULT_CONFIG_OBJECT
+        if self.rays.max_child_rays() == 0 {
+            return invalid("rays.max_child_rays", "must be greater than 0");
+        }
+
+        if let Some(ProgressiveConfig {
+            pass_samples: Some(0),
+            ..
+        }) = self.render.progressive
+        {
+            return invalid("render.progressive.pass_samples", "must be greater than 0");
+        }
+
+        if let Some(BackgroundConfig::EnvironmentMap {
+            intensity: Some(intensity),
+            ..
+        }) = self.background
+        {
+            if intensity < 0.0 {
+                return invalid("background.intensity", "must not be negative");
+            }
+        }
+
+        Ok(())
     }
 
     /************ Getters ************/
 
+    /// Name of the selected preset, until the config is resolved
+    pub fn preset(&self) -> Option<&str> {
+        self.preset.as_deref()
+    }
+
     pub fn image_config(&self) -> &ImageConfig {
         &self.image
     }
@@ -175,6 +787,52 @@ impl Config {
     pub fn rays_config(&self) -> &RaysConfig {
         &self.rays
     }
+
+    pub fn render_config(&self) -> &RenderConfig {
+        &self.render
+    }
+
+    pub fn image_config_mut(&mut self) -> &mut ImageConfig {
+        &mut self.image
+    }
+
+    pub fn render_config_mut(&mut self) -> &mut RenderConfig {
+        &mut self.render
+    }
+
+    pub fn background_config(&self) -> &BackgroundConfig {
+        self.background.as_ref().unwrap()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        DEFAULT_CONFIG_OBJECT
+    }
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG_OBJECT.image
+    }
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG_OBJECT.camera
+    }
+}
+
+impl Default for RaysConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG_OBJECT.rays
+    }
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG_OBJECT.render
+    }
 }
 
 const DEFAULT_CONFIG_OBJECT: Config = Config {
@@ -182,16 +840,37 @@ const DEFAULT_CONFIG_OBJECT: Config = Config {
         image_width: 256,
         image_height: 256,
         gamma: Some(1.0),
-        aa_mode: Some(AntialiasingMode::None),
+        aa: Some(AntialiasingMode::None {}),
+        sampler: Some(SamplerMode::Independent),
+        output_path: None,
+        output_format: None,
     },
     camera: CameraConfig {
-        viewport_width: 2,
-        viewport_height: 2,
-        origin: Some(Vec3::new(0.0, 0.0, 0.0)),
-        focal_length: Some(1.0),
+        look_from: Some(Vec3::new(0.0, 0.0, 0.0)),
+        look_at: Some(Vec3::new(0.0, 0.0, -1.0)),
+        vup: Some(Vec3::new(0.0, 1.0, 0.0)),
+        vertical_fov: Some(90.0),
+        aperture: Some(0.0),
+        focus_distance: None,
     },
     rays: RaysConfig {
         max_child_rays: Some(50),
         diffuse_scatter_mode: Some(DiffuseScatterMode::TrueLambert),
+        russian_roulette: Some(false),
+        roulette_min_depth: Some(DEFAULT_ROULETTE_MIN_DEPTH),
+    },
+    render: RenderConfig {
+        threads: None,
+        seed: None,
+        acceleration: Some(AccelerationStructure::BVH),
+        integrator: Some(IntegratorMode::Recursive),
+        progressive: None,
+        progress: Some(true),
     },
+    background: Some(BackgroundConfig::Gradient {
+        bottom: COLOR_WHITE,
+        top: Color::new(0.5, 0.7, 1.0),
+    }),
+    preset: None,
+    presets: BTreeMap::new(),
 };
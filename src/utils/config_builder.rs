@@ -0,0 +1,426 @@
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use toml::value::{Table, Value};
+
+use super::config::{Config, ConfigError};
+
+// Prefix of the environment variables overriding config options
+pub const ENV_PREFIX: &str = "RAYTRACER_";
+
+// Sections of the config that environment variables can set options in
+const CONFIG_SECTIONS: [&str; 5] = ["image", "camera", "rays", "render", "background"];
+
+// Keys selecting the variant of a tagged option, e.g. `aa = { mode = "MSAA" }`
+const TAG_KEYS: [&str; 2] = ["mode", "type"];
+
+// Options renamed since earlier versions, as (section, former key, key)
+const RENAMED_OPTIONS: [(&str, &str, &str); 1] = [("image", "aa_mode", "aa")];
+
+/// The `[settings]` table of a scene file, read to locate errors in its options
+#[derive(Deserialize)]
+struct SceneSettings {
+    settings: Option<Config>,
+}
+
+/// Builds a config from layers of TOML options, each layer overriding the options of
+/// the layers before it. The first layer holds the built-in defaults, followed by a
+/// preset named in the config layers, the config files and scene settings, a preset
+/// selected with `select_preset` and the environment variables. Every layer is
+/// checked when it is added, and a layer with invalid options is left out
+pub struct ConfigBuilder {
+    d_options: Value,
+    d_layers: Value,
+    d_preset: Option<String>,
+    d_env_layers: Value,
+}
+
+impl ConfigBuilder {
+    /********* Initializers *********/
+
+    pub fn new() -> Self {
+        ConfigBuilder {
+            d_options: to_options(&Config::default()),
+            d_layers: Value::Table(Table::new()),
+            d_preset: None,
+            d_env_layers: Value::Table(Table::new()),
+        }
+    }
+
+    /************ Layers ************/
+
+    /// Add the options of a config file
+    pub fn add_file(&mut self, config_toml_file: &str) -> Result<(), ConfigError> {
+        let toml_content =
+            std::fs::read_to_string(config_toml_file).map_err(|error| ConfigError::Io {
+                path: config_toml_file.to_string(),
+                error,
+            })?;
+
+        self.add_toml_str(&toml_content)
+            .map_err(|config_error| config_error.in_file(config_toml_file))
+    }
+
+    /// Add the options of the `[settings]` table of a scene file, if it has one
+    pub fn add_scene_settings(&mut self, scene_toml_file: &str) -> Result<(), ConfigError> {
+        let toml_content = match std::fs::read_to_string(scene_toml_file) {
+            Ok(toml_content) => toml_content,
+            // A missing scene is reported when the scene is loaded
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => {
+                return Err(ConfigError::Io {
+                    path: scene_toml_file.to_string(),
+                    error,
+                })
+            }
+        };
+
+        let scene: Table = parse_toml(&toml_content)
+            .map_err(|config_error| config_error.in_file(scene_toml_file))?;
+        match scene.get("settings") {
+            Some(Value::Table(settings)) => self
+                .add_table(settings.clone(), || {
+                    toml::from_str::<SceneSettings>(&toml_content).err()
+                })
+                .map_err(|config_error| config_error.in_file(scene_toml_file)),
+            Some(_) => Err(ConfigError::Validation {
+                option: "settings".to_string(),
+                reason: format!("must be a table in '{}'", scene_toml_file),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Add the options of a TOML document
+    pub fn add_toml_str(&mut self, toml_content: &str) -> Result<(), ConfigError> {
+        self.add_table(parse_toml(toml_content)?, || toml::from_str::<Config>(toml_content).err())
+    }
+
+    /// Add the options set by environment variables. `RAYTRACER_RENDER_SEED=7` sets
+    /// `seed` in the `[render]` section, and `RAYTRACER_IMAGE_WIDTH=800` sets
+    /// `image_width`, as options repeating their section name may omit it. A double
+    /// underscore separates nested tables, as in
+    /// `RAYTRACER_RENDER_PROGRESSIVE__PASS_SAMPLES=4`. Values are read as TOML, or as
+    /// strings if they are not valid TOML. `RAYTRACER_PRESET=draft` selects a preset,
+    /// as `select_preset` does. The variables override the selected preset. Each
+    /// variable is added on its own, except that the variables of a tagged
+    /// option such as `aa` are added together. Returns the errors of the variables
+    /// that were left out
+    pub fn add_env<I: IntoIterator<Item = (String, String)>>(
+        &mut self,
+        variables: I,
+    ) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+        let mut options: Vec<(String, Vec<String>, Value)> = Vec::new();
+
+        for (name, value) in variables {
+            let option = match name.strip_prefix(ENV_PREFIX) {
+                Some(option) => option.to_lowercase(),
+                None => continue,
+            };
+
+            if option == "preset" {
+                if let Err(config_error) = self.select_preset(&value) {
+                    errors.push(invalid_variable(&name, config_error));
+                }
+                continue;
+            }
+
+            let (section, key) = option.split_once('_').unwrap_or((&option, ""));
+            if !CONFIG_SECTIONS.contains(&section) || key.is_empty() {
+                errors.push(ConfigError::Validation {
+                    option: name,
+                    reason: "does not name an option of a config section".to_string(),
+                });
+                continue;
+            }
+
+            let value = parse_toml::<Table>(&format!("value = {}", value))
+                .ok()
+                .and_then(|mut table| table.remove("value"))
+                .unwrap_or(Value::String(value));
+
+            let prefixed_key = format!("{}_{}", section, key);
+            let key = match self.d_options.get(section) {
+                Some(Value::Table(defaults))
+                    if !defaults.contains_key(key) && defaults.contains_key(&prefixed_key) =>
+                {
+                    &prefixed_key
+                }
+                _ => key,
+            };
+
+            let mut path = vec![section.to_string()];
+            path.extend(key.split("__").map(str::to_string));
+            options.push((name, path, value));
+        }
+
+        // Group the variables of tagged options, which cannot change variant one
+        // option at a time
+        let mut layers: Vec<(Vec<String>, Table)> = Vec::new();
+        let mut tagged_layers: BTreeMap<Vec<String>, usize> = BTreeMap::new();
+        for (name, path, value) in &options {
+            let parent = path[..path.len() - 1].to_vec();
+            let tagged = is_tagged(&self.d_options, &parent)
+                || options.iter().any(|(_, other_path, _)| {
+                    other_path.len() == path.len()
+                        && other_path.starts_with(&parent)
+                        && TAG_KEYS.contains(&other_path[parent.len()].as_str())
+                });
+
+            let layer = match tagged_layers.get(&parent) {
+                Some(&layer) if tagged => layer,
+                _ => {
+                    layers.push((Vec::new(), Table::new()));
+                    if tagged {
+                        tagged_layers.insert(parent, layers.len() - 1);
+                    }
+                    layers.len() - 1
+                }
+            };
+
+            let (names, table) = &mut layers[layer];
+            names.push(name.clone());
+            insert_option(table, path, value.clone());
+        }
+
+        for (names, table) in layers {
+            if let Err(config_error) = self.add_env_table(table) {
+                errors.push(invalid_variable(&names.join(", "), config_error));
+            }
+        }
+
+        errors
+    }
+
+    /// Merge the options set by environment variables over those of every other layer,
+    /// unless the result is invalid
+    fn add_env_table(&mut self, mut options: Table) -> Result<(), ConfigError> {
+        rename_options(&mut options);
+        let mut env_layers = self.d_env_layers.clone();
+        merge(&mut env_layers, Value::Table(options.clone()));
+
+        check(&self.d_layers, self.d_preset.as_deref(), &env_layers)?;
+        self.d_env_layers = env_layers;
+        merge(&mut self.d_options, Value::Table(options));
+        Ok(())
+    }
+
+    /// Merge the options over those of the config layers before, unless the result is
+    /// invalid. `locate` reads the layer on its own to find the position of a parse
+    /// error
+    fn add_table(
+        &mut self,
+        mut options: Table,
+        locate: impl FnOnce() -> Option<toml::de::Error>,
+    ) -> Result<(), ConfigError> {
+        rename_options(&mut options);
+        let mut layers = self.d_layers.clone();
+        merge(&mut layers, Value::Table(options.clone()));
+
+        match check(&layers, self.d_preset.as_deref(), &self.d_env_layers) {
+            Ok(_) => {
+                self.d_layers = layers;
+                merge(&mut self.d_options, Value::Table(options));
+                Ok(())
+            }
+            Err(ConfigError::Parse { message, .. }) => match locate() {
+                Some(toml_error) if toml_error.line_col().is_some() => Err(ConfigError::Parse {
+                    path: None,
+                    message: toml_error.to_string(),
+                    position: toml_error.line_col(),
+                }),
+                _ => Err(ConfigError::Parse {
+                    path: None,
+                    message,
+                    position: None,
+                }),
+            },
+            Err(config_error) => Err(config_error),
+        }
+    }
+
+    /************ Builders ************/
+
+    /// Select the preset overriding the options of the config files and scene
+    /// settings, replacing any preset they name. Environment variables still override
+    /// it
+    pub fn select_preset(&mut self, name: &str) -> Result<(), ConfigError> {
+        check(&self.d_layers, Some(name), &self.d_env_layers)?;
+        self.d_preset = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Options of all layers merged over the defaults, without those of the preset
+    pub fn options(&self) -> &Value {
+        &self.d_options
+    }
+
+    /// Create the config from the merged options and check it
+    pub fn build(&self) -> Result<Config, ConfigError> {
+        check(&self.d_layers, self.d_preset.as_deref(), &self.d_env_layers)
+    }
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        ConfigBuilder::new()
+    }
+}
+
+fn parse_toml<T: serde::de::DeserializeOwned>(toml_content: &str) -> Result<T, ConfigError> {
+    toml::from_str(toml_content).map_err(|toml_error| ConfigError::Parse {
+        path: None,
+        message: toml_error.to_string(),
+        position: toml_error.line_col(),
+    })
+}
+
+/// Report an error in the options set by environment variables under their names
+fn invalid_variable(names: &str, config_error: ConfigError) -> ConfigError {
+    ConfigError::Validation {
+        option: names.to_string(),
+        reason: match config_error {
+            ConfigError::Parse { message, .. } => message,
+            ConfigError::Validation { option, reason } => format!("{} {}", option, reason),
+            config_error => config_error.to_string(),
+        },
+    }
+}
+
+/// Whether the table at the path selects its variant with a tag key
+fn is_tagged(options: &Value, path: &[String]) -> bool {
+    let mut value = options;
+    for key in path {
+        match value.get(key) {
+            Some(nested) => value = nested,
+            None => return false,
+        }
+    }
+
+    TAG_KEYS.iter().any(|tag| value.get(*tag).is_some())
+}
+
+/// Set the option at the path, creating the tables it is nested in
+fn insert_option(options: &mut Table, path: &[String], value: Value) {
+    let (last, tables) = path.split_last().unwrap();
+    let mut table = options;
+    for name in tables {
+        table = match table
+            .entry(name.to_string())
+            .or_insert_with(|| Value::Table(Table::new()))
+        {
+            Value::Table(nested) => nested,
+            _ => unreachable!("Tables are only nested in tables"),
+        };
+    }
+    table.insert(last.to_string(), value);
+}
+
+/// Create the config from the config layers and the environment layers merged over
+/// the defaults and check it. The options of a preset named in the config layers are
+/// set in the defaults, so that every layer overrides them, while a selected preset
+/// overrides the config layers
+fn check(
+    layers: &Value,
+    selected_preset: Option<&str>,
+    env_layers: &Value,
+) -> Result<Config, ConfigError> {
+    let mut defaults = Config::default();
+
+    // Read the preset selection before the options that may depend on the preset
+    let selection = Table::from_iter(
+        ["preset", "presets"]
+            .into_iter()
+            .filter_map(|key| Some((key.to_string(), layers.get(key)?.clone()))),
+    );
+    let selection = layered_config(&defaults, &Value::Table(selection))?;
+
+    let mut env_layers = env_layers.clone();
+    let config = match selected_preset {
+        Some(name) => {
+            let mut config = layered_config(&defaults, layers)?;
+            config.apply_preset(&selection.find_preset(name)?);
+            // The resolution scale of the preset is applied when resolving the config
+            merge(
+                &mut env_layers,
+                Value::Table(Table::from_iter([(
+                    "preset".to_string(),
+                    Value::String(name.to_string()),
+                )])),
+            );
+            config
+        }
+        None => {
+            if let Some(name) = selection.preset() {
+                defaults.apply_preset(&selection.find_preset(name)?);
+            }
+            layered_config(&defaults, layers)?
+        }
+    };
+
+    layered_config(&config, &env_layers)?.resolve()
+}
+
+fn layered_config(base: &Config, layers: &Value) -> Result<Config, ConfigError> {
+    let mut options = to_options(base);
+    merge(&mut options, layers.clone());
+
+    options
+        .try_into()
+        .map_err(|toml_error: toml::de::Error| ConfigError::Parse {
+            path: None,
+            message: toml_error.to_string(),
+            position: None,
+        })
+}
+
+fn to_options(config: &Config) -> Value {
+    Value::try_from(config).expect("Configs are representable in TOML")
+}
+
+/// Move options set under their former names to their current names, so that they
+/// override the options of earlier layers
+fn rename_options(options: &mut Table) {
+    for (section, former_key, key) in RENAMED_OPTIONS {
+        if let Some(Value::Table(section)) = options.get_mut(section) {
+            // Setting both names is left for deserialization to report
+            if !section.contains_key(key) {
+                if let Some(value) = section.remove(former_key) {
+                    section.insert(key.to_string(), value);
+                }
+            }
+        }
+    }
+}
+
+/// Merge the options of `overlay` into `base`. Tables are merged key by key, except
+/// for tagged options switching to another variant, which replace the base table
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Table(base_table), Value::Table(overlay_table))
+            if !changes_variant(base_table, &overlay_table) =>
+        {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) => merge(base_value, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn changes_variant(base: &Table, overlay: &Table) -> bool {
+    TAG_KEYS
+        .iter()
+        .any(|tag| match (base.get(*tag), overlay.get(*tag)) {
+            (Some(base_variant), Some(overlay_variant)) => base_variant != overlay_variant,
+            _ => false,
+        })
+}
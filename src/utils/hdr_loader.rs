@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::types::{color::Color, framebuffer::Framebuffer};
+
+// Scanline widths that new-style run-length encoding supports
+const RLE_WIDTHS: std::ops::Range<usize> = 8..0x8000;
+
+/// Errors raised while loading Radiance HDR images
+#[derive(Debug)]
+pub enum HdrError {
+    Io { path: String, error: std::io::Error },
+    Format { path: String, message: String },
+}
+
+impl Display for HdrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HdrError::Io { path, error } => write!(f, "Unable to read '{}': {}", path, error),
+            HdrError::Format { path, message } => write!(f, "{}: {}", path, message),
+        }
+    }
+}
+
+/// Load a Radiance `.hdr` (RGBE) image into a framebuffer of linear colors
+pub fn load_hdr(hdr_file: &str) -> Result<Framebuffer, HdrError> {
+    let content = std::fs::read(hdr_file).map_err(|error| HdrError::Io {
+        path: hdr_file.to_string(),
+        error,
+    })?;
+
+    parse_hdr(&content).map_err(|message| HdrError::Format {
+        path: hdr_file.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Decode the content of a Radiance `.hdr` file, either flat or run-length encoded
+pub fn parse_hdr(content: &[u8]) -> Result<Framebuffer, &'static str> {
+    let mut position = 0;
+
+    let magic = read_line(content, &mut position)?;
+    if !magic.starts_with("#?") {
+        return Err("Missing Radiance header");
+    }
+
+    // Header variables end with an empty line
+    loop {
+        let line = read_line(content, &mut position)?;
+        if line.is_empty() {
+            break;
+        }
+        if let Some(format) = line.strip_prefix("FORMAT=") {
+            if format.trim() != "32-bit_rle_rgbe" {
+                return Err("Only the 32-bit_rle_rgbe pixel format is supported");
+            }
+        }
+    }
+
+    let resolution = read_line(content, &mut position)?;
+    let (height, width) = match resolution.split_whitespace().collect::<Vec<_>>()[..] {
+        ["-Y", height, "+X", width] => (
+            height
+                .parse::<usize>()
+                .map_err(|_| "Invalid image height")?,
+            width.parse::<usize>().map_err(|_| "Invalid image width")?,
+        ),
+        _ => return Err("Only the standard -Y H +X W orientation is supported"),
+    };
+    if width == 0 || height == 0 {
+        return Err("Image width and height must be greater than 0");
+    }
+    // Check the size in the header against the pixel data before allocating the image
+    let encodable = min_scanline_size(width)
+        .and_then(|size| size.checked_mul(height))
+        .is_some_and(|size| size <= content.len() - position);
+    if !encodable {
+        return Err("Image size exceeds the pixel data");
+    }
+
+    let mut framebuffer = Framebuffer::new(width, height);
+    let mut scanline = vec![[0u8; 4]; width];
+    for y in 0..height {
+        read_scanline(content, &mut position, &mut scanline)?;
+        for (x, rgbe) in scanline.iter().enumerate() {
+            framebuffer.set_pixel(x, y, &rgbe_to_color(rgbe));
+        }
+    }
+
+    Ok(framebuffer)
+}
+
+fn read_line<'a>(content: &'a [u8], position: &mut usize) -> Result<&'a str, &'static str> {
+    let rest = &content[*position..];
+    let length = rest
+        .iter()
+        .position(|&byte| byte == b'\n')
+        .ok_or("Unexpected end of header")?;
+    *position += length + 1;
+
+    std::str::from_utf8(&rest[..length]).map_err(|_| "Header is not valid text")
+}
+
+/// Fewest bytes that can encode a scanline of the width
+fn min_scanline_size(width: usize) -> Option<usize> {
+    if RLE_WIDTHS.contains(&width) {
+        // The scanline header, then runs of at most 127 values in each channel
+        Some(4 + 4 * 2 * width.div_ceil(127))
+    } else {
+        width.checked_mul(4)
+    }
+}
+
+fn read_scanline(
+    content: &[u8],
+    position: &mut usize,
+    scanline: &mut [[u8; 4]],
+) -> Result<(), &'static str> {
+    let width = scanline.len();
+    let rest = &content[*position..];
+
+    // New-style run-length encoding starts with 2, 2 and the scanline width
+    let is_rle = RLE_WIDTHS.contains(&width)
+        && rest.len() >= 4
+        && rest[0] == 2
+        && rest[1] == 2
+        && ((rest[2] as usize) << 8 | rest[3] as usize) == width;
+
+    if !is_rle {
+        let bytes = rest
+            .get(..width * 4)
+            .ok_or("Unexpected end of pixel data")?;
+        for (pixel, rgbe) in scanline.iter_mut().zip(bytes.chunks_exact(4)) {
+            pixel.copy_from_slice(rgbe);
+        }
+        *position += width * 4;
+        return Ok(());
+    }
+
+    *position += 4;
+    // Each of the four channels is encoded separately
+    for channel in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let count = *content
+                .get(*position)
+                .ok_or("Unexpected end of pixel data")?;
+            *position += 1;
+
+            if count > 128 {
+                let run = (count - 128) as usize;
+                let value = *content
+                    .get(*position)
+                    .ok_or("Unexpected end of pixel data")?;
+                *position += 1;
+                if x + run > width {
+                    return Err("Run exceeds scanline width");
+                }
+                for pixel in &mut scanline[x..x + run] {
+                    pixel[channel] = value;
+                }
+                x += run;
+            } else {
+                let run = count as usize;
+                if run == 0 || x + run > width {
+                    return Err("Invalid run length");
+                }
+                let values = content
+                    .get(*position..*position + run)
+                    .ok_or("Unexpected end of pixel data")?;
+                *position += run;
+                for (pixel, value) in scanline[x..x + run].iter_mut().zip(values) {
+                    pixel[channel] = *value;
+                }
+                x += run;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a shared-exponent pixel to linear color
+fn rgbe_to_color(rgbe: &[u8; 4]) -> Color {
+    if rgbe[3] == 0 {
+        return Color::zero_vec();
+    }
+
+    let scale = 2f64.powi(rgbe[3] as i32 - 136);
+    Color::new(rgbe[0] as f64 * scale, rgbe[1] as f64 * scale, rgbe[2] as f64 * scale)
+}
@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use crate::types::{color, framebuffer::Framebuffer};
+
+/// Errors raised while loading PNG and PPM images
+#[derive(Debug)]
+pub enum ImageError {
+    Io { path: String, error: std::io::Error },
+    Format { path: String, message: String },
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Io { path, error } => write!(f, "Unable to read '{}': {}", path, error),
+            ImageError::Format { path, message } => write!(f, "{}: {}", path, message),
+        }
+    }
+}
+
+/// Load a PNG or PPM image, converting its gamma encoded pixels to linear colors
+pub fn read_image(image_file: &str, gamma: f64) -> Result<Framebuffer, ImageError> {
+    let content = std::fs::read(image_file).map_err(|error| ImageError::Io {
+        path: image_file.to_string(),
+        error,
+    })?;
+
+    // The format is recognized from the file signature rather than the extension
+    let image = if content.starts_with(b"\x89PNG") {
+        read_png(&content, gamma)
+    } else if content.starts_with(b"P3") || content.starts_with(b"P6") {
+        read_ppm(&content, gamma)
+    } else {
+        Err("Unsupported image format, expected PNG or PPM".to_string())
+    };
+
+    image.map_err(|message| ImageError::Format {
+        path: image_file.to_string(),
+        message,
+    })
+}
+
+/// Decode PNG data of any bit depth and color type
+pub fn read_png(content: &[u8], gamma: f64) -> Result<Framebuffer, String> {
+    let mut decoder = png::Decoder::new(content);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info().map_err(|error| error.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|error| error.to_string())?;
+
+    let channels = info.color_type.samples();
+    let mut framebuffer = Framebuffer::new(info.width as usize, info.height as usize);
+    for (index, pixel) in buffer[..info.buffer_size()]
+        .chunks_exact(channels)
+        .enumerate()
+    {
+        let rgb = match channels {
+            1 | 2 => [pixel[0]; 3],
+            _ => [pixel[0], pixel[1], pixel[2]],
+        };
+        framebuffer.set_pixel(
+            index % framebuffer.width(),
+            index / framebuffer.width(),
+            &color::from_rgb8(&rgb, gamma),
+        );
+    }
+
+    Ok(framebuffer)
+}
+
+/// Decode plain (P3) or binary (P6) PPM data
+pub fn read_ppm(content: &[u8], gamma: f64) -> Result<Framebuffer, String> {
+    let mut position = 0;
+    let mut header = [0usize; 3];
+    let magic = next_ppm_token(content, &mut position).ok_or("Missing PPM header")?;
+    for value in header.iter_mut() {
+        let token = next_ppm_token(content, &mut position).ok_or("Incomplete PPM header")?;
+        *value = token
+            .parse()
+            .map_err(|_| format!("Invalid PPM header value '{}'", token))?;
+    }
+    let [width, height, max_value] = header;
+    if max_value == 0 || max_value > 255 {
+        return Err("Only PPM images with 8-bit samples are supported".to_string());
+    }
+    let sample_count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or("PPM image is too large")?;
+
+    let samples: Vec<u8> = match magic.as_str() {
+        "P6" => {
+            // A single whitespace byte separates the header from the pixel data
+            let start = position + 1;
+            content
+                .get(start..)
+                .and_then(|pixel_data| pixel_data.get(..sample_count))
+                .ok_or("Unexpected end of pixel data")?
+                .to_vec()
+        }
+        "P3" => {
+            // Every sample takes at least one byte, which bounds the allocation
+            let mut samples = Vec::with_capacity(sample_count.min(content.len()));
+            for _ in 0..sample_count {
+                let token =
+                    next_ppm_token(content, &mut position).ok_or("Unexpected end of pixel data")?;
+                samples.push(
+                    token
+                        .parse::<u8>()
+                        .map_err(|_| format!("Invalid PPM sample '{}'", token))?,
+                );
+            }
+            samples
+        }
+        _ => return Err(format!("Unsupported PPM type '{}'", magic)),
+    };
+    if let Some(sample) = samples.iter().find(|&&sample| sample as usize > max_value) {
+        return Err(format!("PPM sample {} exceeds the maximum value {}", sample, max_value));
+    }
+
+    let mut framebuffer = Framebuffer::new(width, height);
+    for (index, pixel) in samples.chunks_exact(3).enumerate() {
+        // Rescale samples to the full 8-bit range
+        let rgb = [
+            (pixel[0] as usize * 255 / max_value) as u8,
+            (pixel[1] as usize * 255 / max_value) as u8,
+            (pixel[2] as usize * 255 / max_value) as u8,
+        ];
+        framebuffer.set_pixel(index % width, index / width, &color::from_rgb8(&rgb, gamma));
+    }
+
+    Ok(framebuffer)
+}
+
+/// Next whitespace separated token of a PPM header, skipping `#` comments
+fn next_ppm_token(content: &[u8], position: &mut usize) -> Option<String> {
+    loop {
+        match content.get(*position)? {
+            byte if byte.is_ascii_whitespace() => *position += 1,
+            b'#' => {
+                while *content.get(*position)? != b'\n' {
+                    *position += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let start = *position;
+    while content
+        .get(*position)
+        .is_some_and(|byte| !byte.is_ascii_whitespace())
+    {
+        *position += 1;
+    }
+
+    Some(String::from_utf8_lossy(&content[start..*position]).into_owned())
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::types::framebuffer::Framebuffer;
+use crate::utils::config::ImageFormat;
+
+/// Write the framebuffer to a file in the given format
+pub fn write_image(
+    framebuffer: &Framebuffer,
+    output_path: &str,
+    format: &ImageFormat,
+    gamma: f64,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(output_path)?);
+    match format {
+        ImageFormat::PNG => write_png(framebuffer, &mut writer, gamma)?,
+        ImageFormat::PPM => write_ppm(framebuffer, &mut writer, gamma)?,
+    }
+    writer.flush()
+}
+
+/// Write 8-bit RGB PNG data
+pub fn write_png<W: Write>(
+    framebuffer: &Framebuffer,
+    writer: W,
+    gamma: f64,
+) -> std::io::Result<()> {
+    let mut encoder =
+        png::Encoder::new(writer, framebuffer.width() as u32, framebuffer.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut png_writer = encoder.write_header().map_err(std::io::Error::from)?;
+    png_writer
+        .write_image_data(&framebuffer.to_rgb8(gamma))
+        .map_err(std::io::Error::from)
+}
+
+/// Write binary (P6) PPM data
+pub fn write_ppm<W: Write>(
+    framebuffer: &Framebuffer,
+    mut writer: W,
+    gamma: f64,
+) -> std::io::Result<()> {
+    write!(writer, "P6\n{} {}\n255\n", framebuffer.width(), framebuffer.height())?;
+    writer.write_all(&framebuffer.to_rgb8(gamma))
+}
@@ -0,0 +1,490 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::components::{traceable::TraceableGroup, triangle_mesh::TriangleMesh};
+use crate::types::{
+    color::Color, dielectric_mat::DielectricMaterial, diffuse_light::DiffuseLight,
+    diffuse_mat::DiffuseMaterial, hit_record::Point3, material::Material, metal_mat::MetalMaterial,
+    vec3::Vec3,
+};
+
+/// Errors raised while loading OBJ and MTL files
+#[derive(Debug)]
+pub enum ObjError {
+    Io {
+        path: String,
+        error: std::io::Error,
+    },
+    Parse {
+        path: String,
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Io { path, error } => write!(f, "Unable to read '{}': {}", path, error),
+            ObjError::Parse {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", path, line, message),
+        }
+    }
+}
+
+/// Indices of the position, texture coordinate and normal of one face corner
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct ObjVertex {
+    position: usize,
+    texture_coord: Option<usize>,
+    normal: Option<usize>,
+}
+
+/// Triangles sharing the material selected by a `usemtl` statement
+#[derive(Debug)]
+struct FaceGroup {
+    material: Option<String>,
+    line: usize,
+    triangles: Vec<[ObjVertex; 3]>,
+}
+
+/// Geometry parsed from a Wavefront OBJ file
+#[derive(Debug)]
+pub struct ObjModel {
+    d_source: String,
+    d_positions: Vec<Point3>,
+    d_texture_coords: Vec<(f64, f64)>,
+    d_normals: Vec<Vec3>,
+    d_groups: Vec<FaceGroup>,
+    d_material_libraries: Vec<String>,
+}
+
+impl ObjModel {
+    /********* Initializers *********/
+
+    pub fn from_file(obj_file: &str) -> Result<Self, ObjError> {
+        let content = std::fs::read_to_string(obj_file).map_err(|error| ObjError::Io {
+            path: obj_file.to_string(),
+            error,
+        })?;
+
+        ObjModel::from_obj_str(content.as_str(), obj_file)
+    }
+
+    /// Parse OBJ content. The source name is only used in error messages
+    pub fn from_obj_str(content: &str, source: &str) -> Result<Self, ObjError> {
+        let mut model = ObjModel {
+            d_source: source.to_string(),
+            d_positions: vec![],
+            d_texture_coords: vec![],
+            d_normals: vec![],
+            d_groups: vec![],
+            d_material_libraries: vec![],
+        };
+
+        for (line_index, line) in content.lines().enumerate() {
+            let parse_error = |message: String| ObjError::Parse {
+                path: source.to_string(),
+                line: line_index + 1,
+                message,
+            };
+
+            let mut tokens = line.split('#').next().unwrap_or("").split_whitespace();
+            let keyword = match tokens.next() {
+                Some(keyword) => keyword,
+                None => continue,
+            };
+            let arguments: Vec<&str> = tokens.collect();
+
+            match keyword {
+                "v" => {
+                    let coordinates = parse_floats(&arguments, 3, 4).map_err(parse_error)?;
+                    model.d_positions.push(Vec3::new(
+                        coordinates[0],
+                        coordinates[1],
+                        coordinates[2],
+                    ));
+                }
+                "vn" => {
+                    let coordinates = parse_floats(&arguments, 3, 3).map_err(parse_error)?;
+                    model
+                        .d_normals
+                        .push(Vec3::new(coordinates[0], coordinates[1], coordinates[2]));
+                }
+                "vt" => {
+                    let coordinates = parse_floats(&arguments, 1, 3).map_err(parse_error)?;
+                    model
+                        .d_texture_coords
+                        .push((coordinates[0], *coordinates.get(1).unwrap_or(&0.0)));
+                }
+                "f" => {
+                    if arguments.len() < 3 {
+                        return Err(parse_error(format!(
+                            "Face needs at least 3 vertices, found {}",
+                            arguments.len()
+                        )));
+                    }
+
+                    let mut corners = Vec::with_capacity(arguments.len());
+                    for argument in &arguments {
+                        corners.push(model.parse_face_vertex(argument).map_err(parse_error)?);
+                    }
+
+                    if model.d_groups.is_empty() {
+                        model.d_groups.push(FaceGroup {
+                            material: None,
+                            line: line_index + 1,
+                            triangles: vec![],
+                        });
+                    }
+
+                    // Triangulate polygons as a fan around the first corner
+                    let group = model.d_groups.last_mut().unwrap();
+                    for i in 1..corners.len() - 1 {
+                        group
+                            .triangles
+                            .push([corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+                "usemtl" => {
+                    let material = arguments
+                        .first()
+                        .ok_or_else(|| parse_error("usemtl needs a material name".to_string()))?;
+                    model.d_groups.push(FaceGroup {
+                        material: Some(material.to_string()),
+                        line: line_index + 1,
+                        triangles: vec![],
+                    });
+                }
+                "mtllib" => {
+                    if arguments.is_empty() {
+                        return Err(parse_error("mtllib needs a file name".to_string()));
+                    }
+                    model
+                        .d_material_libraries
+                        .extend(arguments.iter().map(|library| library.to_string()));
+                }
+                // Grouping, smoothing groups and other statements do not affect the geometry
+                _ => {}
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Resolve a `v`, `v/vt`, `v//vn` or `v/vt/vn` face vertex. Negative indices
+    /// count back from the most recently defined element
+    fn parse_face_vertex(&self, argument: &str) -> Result<ObjVertex, String> {
+        let mut parts = argument.split('/');
+
+        let position = resolve_index(parts.next(), self.d_positions.len(), "Vertex")?
+            .ok_or_else(|| format!("Face vertex '{}' has no position index", argument))?;
+        let texture_coord =
+            resolve_index(parts.next(), self.d_texture_coords.len(), "Texture coordinate")?;
+        let normal = resolve_index(parts.next(), self.d_normals.len(), "Normal")?;
+
+        Ok(ObjVertex {
+            position,
+            texture_coord,
+            normal,
+        })
+    }
+
+    /************ Getters ************/
+
+    pub fn material_libraries(&self) -> &[String] {
+        &self.d_material_libraries
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.d_groups
+            .iter()
+            .map(|group| group.triangles.len())
+            .sum()
+    }
+
+    /************ Builders ************/
+
+    /// Load the material libraries referenced by the model, relative to the model file
+    pub fn load_materials(&self) -> Result<HashMap<String, Box<dyn Material>>, ObjError> {
+        let directory = Path::new(&self.d_source)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+
+        let mut materials = HashMap::new();
+        for library in &self.d_material_libraries {
+            let library_path = directory.join(library);
+            materials.extend(load_mtl(library_path.to_string_lossy().as_ref())?);
+        }
+
+        Ok(materials)
+    }
+
+    /// Create one mesh per material group. Faces use `material` if given, otherwise the
+    /// material named by their `usemtl` statement, falling back to a grey diffuse material
+    pub fn build(
+        &self,
+        material: Option<&dyn Material>,
+        materials: &HashMap<String, Box<dyn Material>>,
+    ) -> Result<TraceableGroup, ObjError> {
+        let default_material: Box<dyn Material> =
+            Box::new(DiffuseMaterial::new(&Color::new(0.5, 0.5, 0.5)));
+
+        let mut meshes = TraceableGroup { objects: vec![] };
+        for group in self
+            .d_groups
+            .iter()
+            .filter(|group| !group.triangles.is_empty())
+        {
+            let group_material = match (material, group.material.as_ref()) {
+                (Some(material), _) => material.clone_box(),
+                (None, Some(name)) => materials
+                    .get(name)
+                    .ok_or_else(|| ObjError::Parse {
+                        path: self.d_source.clone(),
+                        line: group.line,
+                        message: format!("Unknown material '{}'", name),
+                    })?
+                    .clone(),
+                (None, None) => default_material.clone(),
+            };
+
+            meshes.add(Box::new(self.build_mesh(group, group_material)?));
+        }
+
+        Ok(meshes)
+    }
+
+    fn build_mesh(
+        &self,
+        group: &FaceGroup,
+        material: Box<dyn Material>,
+    ) -> Result<TriangleMesh, ObjError> {
+        // Only smooth shade the group if every corner has a normal
+        let smooth = group
+            .triangles
+            .iter()
+            .flatten()
+            .all(|corner| corner.normal.is_some());
+        let textured = group
+            .triangles
+            .iter()
+            .flatten()
+            .all(|corner| corner.texture_coord.is_some());
+
+        // Corners with the same attributes share one mesh vertex
+        let mut vertex_indices: HashMap<ObjVertex, usize> = HashMap::new();
+        let mut vertices = vec![];
+        let mut normals = vec![];
+        let mut texture_coords = vec![];
+        let mut indices = Vec::with_capacity(group.triangles.len());
+
+        for triangle in &group.triangles {
+            let mut face = [0; 3];
+            for (corner_index, corner) in triangle.iter().enumerate() {
+                face[corner_index] = *vertex_indices.entry(*corner).or_insert_with(|| {
+                    vertices.push(self.d_positions[corner.position]);
+                    if smooth {
+                        normals.push(self.d_normals[corner.normal.unwrap()]);
+                    }
+                    if textured {
+                        texture_coords.push(self.d_texture_coords[corner.texture_coord.unwrap()]);
+                    }
+                    vertices.len() - 1
+                });
+            }
+            indices.push(face);
+        }
+
+        TriangleMesh::with_texture_coords(
+            vertices,
+            smooth.then_some(normals),
+            textured.then_some(texture_coords),
+            indices,
+            material,
+        )
+        .map_err(|message| ObjError::Parse {
+            path: self.d_source.clone(),
+            line: group.line,
+            message: message.to_string(),
+        })
+    }
+}
+
+/// Load the materials of a Wavefront MTL file by name
+pub fn load_mtl(mtl_file: &str) -> Result<HashMap<String, Box<dyn Material>>, ObjError> {
+    let content = std::fs::read_to_string(mtl_file).map_err(|error| ObjError::Io {
+        path: mtl_file.to_string(),
+        error,
+    })?;
+
+    parse_mtl(content.as_str(), mtl_file)
+}
+
+/// Parse MTL content, mapping each material to the closest renderer material:
+/// emissive materials (`Ke`) become lights, transparent materials (`d` < 1) become
+/// dielectrics with `Ni` as the refractive index, materials with a brighter specular
+/// (`Ks`) than diffuse (`Kd`) color become metals and everything else is diffuse
+pub fn parse_mtl(
+    content: &str,
+    source: &str,
+) -> Result<HashMap<String, Box<dyn Material>>, ObjError> {
+    let mut descriptions: Vec<MtlDescription> = vec![];
+
+    for (line_index, line) in content.lines().enumerate() {
+        let parse_error = |message: String| ObjError::Parse {
+            path: source.to_string(),
+            line: line_index + 1,
+            message,
+        };
+
+        let mut tokens = line.split('#').next().unwrap_or("").split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        if keyword == "newmtl" {
+            let name = arguments
+                .first()
+                .ok_or_else(|| parse_error("newmtl needs a material name".to_string()))?;
+            descriptions.push(MtlDescription::new(name));
+            continue;
+        }
+
+        let description = match descriptions.last_mut() {
+            Some(description) => description,
+            None if ["Kd", "Ks", "Ke", "Ni", "Ns", "d", "Tr"].contains(&keyword) => {
+                return Err(parse_error(format!("'{}' before any newmtl statement", keyword)))
+            }
+            None => continue,
+        };
+
+        match keyword {
+            "Kd" | "Ks" | "Ke" => {
+                let rgb = parse_floats(&arguments, 3, 3).map_err(parse_error)?;
+                let color = Color::new(rgb[0], rgb[1], rgb[2]);
+                match keyword {
+                    "Kd" => description.diffuse = color,
+                    "Ks" => description.specular = color,
+                    _ => description.emissive = color,
+                }
+            }
+            "Ni" => description.ior = parse_floats(&arguments, 1, 1).map_err(parse_error)?[0],
+            "Ns" => description.shininess = parse_floats(&arguments, 1, 1).map_err(parse_error)?[0],
+            "d" => description.opacity = parse_floats(&arguments, 1, 1).map_err(parse_error)?[0],
+            "Tr" => {
+                description.opacity = 1.0 - parse_floats(&arguments, 1, 1).map_err(parse_error)?[0]
+            }
+            // Texture maps and illumination models are not supported
+            _ => {}
+        }
+    }
+
+    Ok(descriptions
+        .iter()
+        .map(|description| (description.name.clone(), description.build()))
+        .collect())
+}
+
+struct MtlDescription {
+    name: String,
+    diffuse: Color,
+    specular: Color,
+    emissive: Color,
+    ior: f64,
+    shininess: f64,
+    opacity: f64,
+}
+
+impl MtlDescription {
+    fn new(name: &str) -> Self {
+        MtlDescription {
+            name: name.to_string(),
+            diffuse: Color::new(0.8, 0.8, 0.8),
+            specular: Color::zero_vec(),
+            emissive: Color::zero_vec(),
+            ior: 1.5,
+            shininess: 0.0,
+            opacity: 1.0,
+        }
+    }
+
+    fn build(&self) -> Box<dyn Material> {
+        let max_component = |color: &Color| color.x().max(color.y()).max(color.z());
+
+        if !self.emissive.is_nearly_zero() {
+            Box::new(DiffuseLight::new(&self.emissive))
+        } else if self.opacity < 1.0 {
+            Box::new(DielectricMaterial::new(self.ior))
+        } else if max_component(&self.specular) > max_component(&self.diffuse) {
+            // Map the Phong exponent to roughness, so high exponents are sharp reflections
+            let roughness = (2.0 / (self.shininess.max(0.0) + 2.0)).sqrt();
+            Box::new(MetalMaterial::new(&self.specular, roughness))
+        } else {
+            Box::new(DiffuseMaterial::new(&self.diffuse))
+        }
+    }
+}
+
+/// Parse between min_count and max_count floating point arguments
+fn parse_floats(
+    arguments: &[&str],
+    min_count: usize,
+    max_count: usize,
+) -> Result<Vec<f64>, String> {
+    if arguments.len() < min_count || arguments.len() > max_count {
+        return Err(if min_count == max_count {
+            format!("Expected {} values, found {}", min_count, arguments.len())
+        } else {
+            format!("Expected {} to {} values, found {}", min_count, max_count, arguments.len())
+        });
+    }
+
+    arguments
+        .iter()
+        .map(|argument| {
+            argument
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid number '{}'", argument))
+        })
+        .collect()
+}
+
+/// Convert a 1-based (or negative, relative) OBJ index into a 0-based index
+fn resolve_index(
+    index: Option<&str>,
+    count: usize,
+    element: &str,
+) -> Result<Option<usize>, String> {
+    let index = match index {
+        Some(index) if !index.is_empty() => index,
+        _ => return Ok(None),
+    };
+
+    let value = index
+        .parse::<i64>()
+        .map_err(|_| format!("Invalid {} index '{}'", element.to_lowercase(), index))?;
+
+    let resolved = if value > 0 {
+        value - 1
+    } else {
+        count as i64 + value
+    };
+
+    if value == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(format!(
+            "{} index {} is out of range, {} defined so far",
+            element, value, count
+        ));
+    }
+
+    Ok(Some(resolved as usize))
+}
@@ -0,0 +1,108 @@
+#![allow(dead_code)]
+
+use crate::types::{hit_record::Point3, vec3::Vec3};
+use crate::utils::utilities::{random_f64, random_point_in_unit_sphere, seeded_generator};
+
+// Size of the gradient and permutation tables, must be a power of two
+const POINT_COUNT: usize = 256;
+
+/// Gradient noise over 3D space. The same seed always gives the same noise
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    d_gradients: Vec<Vec3>,
+    d_permute_x: Vec<usize>,
+    d_permute_y: Vec<usize>,
+    d_permute_z: Vec<usize>,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = seeded_generator(seed);
+
+        let gradients = (0..POINT_COUNT)
+            .map(|_| loop {
+                if let Ok(gradient) = random_point_in_unit_sphere(&mut rng).unit_vector() {
+                    break gradient;
+                }
+            })
+            .collect();
+
+        let mut permutation = || {
+            let mut table: Vec<usize> = (0..POINT_COUNT).collect();
+            // Fisher-Yates shuffle
+            for i in (1..POINT_COUNT).rev() {
+                let target = (random_f64(&mut rng) * (i + 1) as f64) as usize;
+                table.swap(i, target);
+            }
+            table
+        };
+
+        Perlin {
+            d_gradients: gradients,
+            d_permute_x: permutation(),
+            d_permute_y: permutation(),
+            d_permute_z: permutation(),
+        }
+    }
+
+    /// Smoothly varying noise in [-1, 1], zero at integer lattice points
+    pub fn noise(&self, point: &Point3) -> f64 {
+        let cell = [point.x().floor(), point.y().floor(), point.z().floor()];
+        let offset = [
+            point.x() - cell[0],
+            point.y() - cell[1],
+            point.z() - cell[2],
+        ];
+        let [i, j, k] = cell.map(|coordinate| coordinate as i64);
+
+        let mut accumulated = 0.0;
+        for di in 0..2 {
+            for dj in 0..2 {
+                for dk in 0..2 {
+                    let gradient = self.d_gradients[self.d_permute_x
+                        [((i + di) as usize) & (POINT_COUNT - 1)]
+                        ^ self.d_permute_y[((j + dj) as usize) & (POINT_COUNT - 1)]
+                        ^ self.d_permute_z[((k + dk) as usize) & (POINT_COUNT - 1)]];
+
+                    let weight = Vec3::new(
+                        offset[0] - di as f64,
+                        offset[1] - dj as f64,
+                        offset[2] - dk as f64,
+                    );
+
+                    // Hermite smoothing of the trilinear blend avoids grid artifacts
+                    let blend = |t: f64, corner: i64| {
+                        let smooth = t * t * (3.0 - 2.0 * t);
+                        if corner == 1 {
+                            smooth
+                        } else {
+                            1.0 - smooth
+                        }
+                    };
+                    accumulated += blend(offset[0], di)
+                        * blend(offset[1], dj)
+                        * blend(offset[2], dk)
+                        * gradient.dot(&weight);
+                }
+            }
+        }
+
+        accumulated
+    }
+
+    /// Sum of noise octaves, each at double the frequency and half the weight
+    /// of the previous one
+    pub fn turbulence(&self, point: &Point3, octaves: u32) -> f64 {
+        let mut accumulated = 0.0;
+        let mut sample_point = *point;
+        let mut weight = 1.0;
+
+        for _ in 0..octaves {
+            accumulated += weight * self.noise(&sample_point);
+            weight *= 0.5;
+            sample_point = sample_point.scaled(2.0);
+        }
+
+        accumulated.abs()
+    }
+}
@@ -0,0 +1,256 @@
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Minimum time between two progress updates on a terminal, and in a log where every
+// update takes a line
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const LOG_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Event counts of one thread, kept thread local so that counting stays cheap
+#[derive(Debug, Copy, Clone, Default)]
+struct Counters {
+    primary_rays: u64,
+    secondary_rays: u64,
+    object_tests: u64,
+    node_tests: u64,
+}
+
+thread_local! {
+    static THREAD_COUNTERS: Cell<Counters> = Cell::new(Counters::default());
+}
+
+// Totals of the counters flushed by every thread
+static PRIMARY_RAYS: AtomicU64 = AtomicU64::new(0);
+static SECONDARY_RAYS: AtomicU64 = AtomicU64::new(0);
+static OBJECT_TESTS: AtomicU64 = AtomicU64::new(0);
+static NODE_TESTS: AtomicU64 = AtomicU64::new(0);
+
+fn update_counters(update: impl FnOnce(&mut Counters)) {
+    THREAD_COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        update(&mut current);
+        counters.set(current);
+    });
+}
+
+/// Count a ray traced into the scene, either from the camera or scattered off a surface
+pub fn count_ray(primary: bool) {
+    update_counters(|counters| match primary {
+        true => counters.primary_rays += 1,
+        false => counters.secondary_rays += 1,
+    });
+}
+
+/// Count a ray intersection test against a sphere or triangle. Groups, meshes and
+/// BVH nodes are not counted, so that every object is counted once
+pub fn count_object_test() {
+    update_counters(|counters| counters.object_tests += 1);
+}
+
+/// Count a ray intersection test against the bounding box of a BVH node
+pub fn count_node_test() {
+    update_counters(|counters| counters.node_tests += 1);
+}
+
+/// Add the counts of the calling thread to the totals. Render threads call this
+/// before they finish, so that their counts are included in `RenderStats::collect`
+pub fn flush_thread_counters() {
+    let counters = THREAD_COUNTERS.with(|counters| counters.replace(Counters::default()));
+
+    PRIMARY_RAYS.fetch_add(counters.primary_rays, Ordering::Relaxed);
+    SECONDARY_RAYS.fetch_add(counters.secondary_rays, Ordering::Relaxed);
+    OBJECT_TESTS.fetch_add(counters.object_tests, Ordering::Relaxed);
+    NODE_TESTS.fetch_add(counters.node_tests, Ordering::Relaxed);
+}
+
+/// Summary of the work done by the renderer since the program started
+#[derive(Debug, Copy, Clone)]
+pub struct RenderStats {
+    d_wall_time: Duration,
+    d_primary_rays: u64,
+    d_secondary_rays: u64,
+    d_object_tests: u64,
+    d_node_tests: u64,
+}
+
+impl RenderStats {
+    /// Gather the counts flushed so far, along with those of the calling thread
+    pub fn collect(wall_time: Duration) -> Self {
+        flush_thread_counters();
+
+        RenderStats {
+            d_wall_time: wall_time,
+            d_primary_rays: PRIMARY_RAYS.load(Ordering::Relaxed),
+            d_secondary_rays: SECONDARY_RAYS.load(Ordering::Relaxed),
+            d_object_tests: OBJECT_TESTS.load(Ordering::Relaxed),
+            d_node_tests: NODE_TESTS.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Gather the counts of the calling thread that are not flushed yet
+    pub fn collect_thread(wall_time: Duration) -> Self {
+        let counters = THREAD_COUNTERS.with(|counters| counters.get());
+
+        RenderStats {
+            d_wall_time: wall_time,
+            d_primary_rays: counters.primary_rays,
+            d_secondary_rays: counters.secondary_rays,
+            d_object_tests: counters.object_tests,
+            d_node_tests: counters.node_tests,
+        }
+    }
+
+    pub fn wall_time(&self) -> Duration {
+        self.d_wall_time
+    }
+
+    pub fn primary_rays(&self) -> u64 {
+        self.d_primary_rays
+    }
+
+    pub fn secondary_rays(&self) -> u64 {
+        self.d_secondary_rays
+    }
+
+    pub fn total_rays(&self) -> u64 {
+        self.d_primary_rays + self.d_secondary_rays
+    }
+
+    pub fn object_tests(&self) -> u64 {
+        self.d_object_tests
+    }
+
+    pub fn node_tests(&self) -> u64 {
+        self.d_node_tests
+    }
+
+    pub fn rays_per_second(&self) -> f64 {
+        self.total_rays() as f64 / self.d_wall_time.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Average number of rays in the path of each camera ray
+    pub fn average_path_depth(&self) -> f64 {
+        if self.d_primary_rays == 0 {
+            return 0.0;
+        }
+
+        self.total_rays() as f64 / self.d_primary_rays as f64
+    }
+}
+
+impl Display for RenderStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Render time:        {}", format_duration(self.d_wall_time))?;
+        writeln!(
+            f,
+            "Rays cast:          {} ({} primary, {} secondary)",
+            self.total_rays(),
+            self.d_primary_rays,
+            self.d_secondary_rays
+        )?;
+        writeln!(f, "Rays per second:    {:.0}", self.rays_per_second())?;
+        writeln!(f, "Average path depth: {:.2}", self.average_path_depth())?;
+        write!(
+            f,
+            "Intersection tests: {} objects, {} BVH nodes",
+            self.d_object_tests, self.d_node_tests
+        )
+    }
+}
+
+/// Reports on stderr how many rows of a pass are done and when it should finish
+pub struct ProgressReporter {
+    d_label: String,
+    d_total_rows: u64,
+    d_completed_rows: AtomicU64,
+    d_start: Instant,
+    d_last_report: Mutex<Option<Instant>>,
+    d_enabled: bool,
+    // Whether stderr is a terminal, where updates rewrite a single line
+    d_terminal: bool,
+}
+
+impl ProgressReporter {
+    pub fn new(label: &str, total_rows: u64, enabled: bool) -> Self {
+        ProgressReporter {
+            d_label: label.to_string(),
+            d_total_rows: total_rows,
+            d_completed_rows: AtomicU64::new(0),
+            d_start: Instant::now(),
+            d_last_report: Mutex::new(None),
+            d_enabled: enabled,
+            d_terminal: std::io::stderr().is_terminal(),
+        }
+    }
+
+    pub fn completed_rows(&self) -> u64 {
+        self.d_completed_rows.load(Ordering::Relaxed)
+    }
+
+    /// Record a finished row, updating the progress at most every few hundred
+    /// milliseconds on a terminal, every few seconds otherwise, and once the last row
+    /// is done
+    pub fn row_done(&self) {
+        let completed = self.d_completed_rows.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.d_enabled {
+            return;
+        }
+
+        let mut last_report = self.d_last_report.lock().unwrap();
+        let now = Instant::now();
+        let finished = completed == self.d_total_rows;
+        let interval = match self.d_terminal {
+            true => PROGRESS_INTERVAL,
+            false => LOG_PROGRESS_INTERVAL,
+        };
+        if !finished && last_report.is_some_and(|last| now - last < interval) {
+            return;
+        }
+        *last_report = Some(now);
+
+        let elapsed = now - self.d_start;
+        let update = if finished {
+            format!(
+                "{}: {}/{} rows (100%) in {}",
+                self.d_label,
+                completed,
+                self.d_total_rows,
+                format_duration(elapsed)
+            )
+        } else {
+            let remaining =
+                elapsed.mul_f64((self.d_total_rows - completed) as f64 / completed as f64);
+            format!(
+                "{}: {}/{} rows ({:.0}%), ETA {}",
+                self.d_label,
+                completed,
+                self.d_total_rows,
+                100.0 * completed as f64 / self.d_total_rows as f64,
+                format_duration(remaining)
+            )
+        };
+
+        match (self.d_terminal, finished) {
+            // Pad over the end of longer updates written on the same line before
+            (true, true) => eprintln!("\r{:<60}", update),
+            (true, false) => eprint!("\r{:<60}", update),
+            (false, _) => eprintln!("{}", update),
+        }
+    }
+}
+
+/// Human readable duration, e.g. `4.2s`, `3m 07s` or `1h 02m 03s`
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..=59 => format!("{:.1}s", duration.as_secs_f64()),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60),
+    }
+}
@@ -0,0 +1,410 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::components::{
+    sphere::Sphere,
+    traceable::{Traceable, TraceableGroup},
+    triangle::Triangle,
+    triangle_mesh::TriangleMesh,
+};
+use crate::types::{
+    checker_tex::{CheckerTexture, UvCheckerTexture},
+    color::{Color, COLOR_BLACK, COLOR_WHITE},
+    dielectric_mat::DielectricMaterial,
+    diffuse_light::DiffuseLight,
+    diffuse_mat::DiffuseMaterial,
+    image_tex::ImageTexture,
+    material::Material,
+    metal_mat::MetalMaterial,
+    noise_tex::{NoiseStyle, NoiseTexture},
+    solid_tex::SolidTexture,
+    texture::Texture,
+    vec3::Vec3,
+};
+use crate::utils::image_reader::read_image;
+use crate::utils::obj_loader::{ObjError, ObjModel};
+
+/// Errors raised while loading or building a scene description
+#[derive(Debug)]
+pub enum SceneError {
+    Io { path: String, error: std::io::Error },
+    Parse { message: String },
+    UnknownMaterial { object: String, material: String },
+    UnknownTexture { material: String, texture: String },
+    InvalidTexture { texture: String, reason: String },
+    InvalidObject { object: String, reason: String },
+}
+
+impl Display for SceneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SceneError::Io { path, error } => {
+                write!(f, "Unable to read scene file '{}': {}", path, error)
+            }
+            SceneError::Parse { message } => write!(f, "Unable to parse scene: {}", message),
+            SceneError::UnknownMaterial { object, material } => {
+                write!(f, "{} references unknown material '{}'", object, material)
+            }
+            SceneError::UnknownTexture { material, texture } => {
+                write!(f, "Material '{}' references unknown texture '{}'", material, texture)
+            }
+            SceneError::InvalidTexture { texture, reason } => {
+                write!(f, "Texture '{}' is invalid: {}", texture, reason)
+            }
+            SceneError::InvalidObject { object, reason } => {
+                write!(f, "{} is invalid: {}", object, reason)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum TextureDescription {
+    Solid {
+        color: Color,
+    },
+    Checker {
+        even: Color,
+        odd: Color,
+        scale: Option<f64>,
+    },
+    UvChecker {
+        even: Color,
+        odd: Color,
+        scale: Option<f64>,
+    },
+    Image {
+        file: String,
+        gamma: Option<f64>,
+    },
+    Noise {
+        style: NoiseStyle,
+        scale: Option<f64>,
+        octaves: Option<u32>,
+        seed: Option<u64>,
+        low: Option<Color>,
+        high: Option<Color>,
+    },
+}
+
+impl TextureDescription {
+    fn build(&self, directory: &Path) -> Result<Arc<dyn Texture>, String> {
+        Ok(match self {
+            TextureDescription::Solid { color } => Arc::new(SolidTexture::new(color)),
+            TextureDescription::Checker { even, odd, scale } => {
+                Arc::new(CheckerTexture::new(even, odd, scale.unwrap_or(1.0)))
+            }
+            TextureDescription::UvChecker { even, odd, scale } => {
+                Arc::new(UvCheckerTexture::new(even, odd, scale.unwrap_or(10.0)))
+            }
+            TextureDescription::Image { file, gamma } => {
+                let image =
+                    read_image(&directory.join(file).to_string_lossy(), gamma.unwrap_or(2.0))
+                        .map_err(|image_error| image_error.to_string())?;
+                Arc::new(ImageTexture::new(image)?)
+            }
+            TextureDescription::Noise {
+                style,
+                scale,
+                octaves,
+                seed,
+                low,
+                high,
+            } => Arc::new(NoiseTexture::new(
+                *style,
+                scale.unwrap_or(1.0),
+                octaves.unwrap_or(7),
+                seed.unwrap_or(0),
+                &low.unwrap_or(COLOR_BLACK),
+                &high.unwrap_or(COLOR_WHITE),
+            )),
+        })
+    }
+}
+
+/// Surface color of a material, either constant or the name of a texture
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AlbedoDescription {
+    Color(Color),
+    Texture(String),
+}
+
+impl AlbedoDescription {
+    /// Resolve the albedo to a texture. Fails with the name of an unknown texture
+    fn build(
+        &self,
+        textures: &HashMap<String, Arc<dyn Texture>>,
+    ) -> Result<Arc<dyn Texture>, String> {
+        match self {
+            AlbedoDescription::Color(color) => Ok(Arc::new(SolidTexture::new(color))),
+            AlbedoDescription::Texture(name) => {
+                textures.get(name).cloned().ok_or_else(|| name.clone())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum MaterialDescription {
+    #[serde(alias = "DiffuseMaterial")]
+    Diffuse { albedo: AlbedoDescription },
+    #[serde(alias = "MetalMaterial")]
+    Metal {
+        albedo: AlbedoDescription,
+        roughness: Option<f64>,
+    },
+    #[serde(alias = "DielectricMaterial")]
+    Dielectric { ior: f64 },
+    #[serde(alias = "Light")]
+    DiffuseLight {
+        color: Color,
+        intensity: Option<f64>,
+    },
+}
+
+impl MaterialDescription {
+    /// Create the material. Fails with the name of an unknown texture
+    fn build(
+        &self,
+        textures: &HashMap<String, Arc<dyn Texture>>,
+    ) -> Result<Box<dyn Material>, String> {
+        Ok(match self {
+            MaterialDescription::Diffuse { albedo } => {
+                Box::new(DiffuseMaterial::from_texture(albedo.build(textures)?))
+            }
+            MaterialDescription::Metal { albedo, roughness } => Box::new(
+                MetalMaterial::from_texture(albedo.build(textures)?, roughness.unwrap_or(0.0)),
+            ),
+            MaterialDescription::Dielectric { ior } => Box::new(DielectricMaterial::new(*ior)),
+            MaterialDescription::DiffuseLight { color, intensity } => {
+                Box::new(DiffuseLight::new(&color.scaled(intensity.unwrap_or(1.0))))
+            }
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum ObjectDescription {
+    Sphere {
+        name: Option<String>,
+        center: Vec3,
+        radius: f64,
+        material: String,
+    },
+    Triangle {
+        name: Option<String>,
+        vertices: [Vec3; 3],
+        normals: Option<[Vec3; 3]>,
+        texture_coords: Option<[(f64, f64); 3]>,
+        material: String,
+    },
+    Mesh {
+        name: Option<String>,
+        vertices: Vec<Vec3>,
+        normals: Option<Vec<Vec3>>,
+        texture_coords: Option<Vec<(f64, f64)>>,
+        indices: Vec<[usize; 3]>,
+        material: String,
+    },
+    Model {
+        name: Option<String>,
+        file: String,
+        material: Option<String>,
+    },
+}
+
+impl ObjectDescription {
+    fn name(&self) -> Option<&String> {
+        match self {
+            ObjectDescription::Sphere { name, .. }
+            | ObjectDescription::Triangle { name, .. }
+            | ObjectDescription::Mesh { name, .. }
+            | ObjectDescription::Model { name, .. } => name.as_ref(),
+        }
+    }
+
+    fn material(&self) -> Option<&String> {
+        match self {
+            ObjectDescription::Sphere { material, .. }
+            | ObjectDescription::Triangle { material, .. }
+            | ObjectDescription::Mesh { material, .. } => Some(material),
+            ObjectDescription::Model { material, .. } => material.as_ref(),
+        }
+    }
+}
+
+/// Declarative description of the objects and materials in a scene
+#[derive(Debug, Deserialize)]
+pub struct SceneDescription {
+    #[serde(default)]
+    textures: HashMap<String, TextureDescription>,
+    #[serde(default)]
+    materials: HashMap<String, MaterialDescription>,
+    #[serde(default)]
+    objects: Vec<ObjectDescription>,
+    // Directory that relative model and image paths are resolved against
+    #[serde(skip)]
+    directory: PathBuf,
+}
+
+impl SceneDescription {
+    /********* Initializers *********/
+
+    pub fn from_toml(scene_toml_file: &str) -> Result<Self, SceneError> {
+        let toml_content =
+            std::fs::read_to_string(scene_toml_file).map_err(|error| SceneError::Io {
+                path: scene_toml_file.to_string(),
+                error,
+            })?;
+
+        let mut scene = SceneDescription::from_toml_str(toml_content.as_str())?;
+        scene.directory = Path::new(scene_toml_file)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Ok(scene)
+    }
+
+    pub fn from_toml_str(toml_content: &str) -> Result<Self, SceneError> {
+        toml::from_str(toml_content).map_err(|toml_error| SceneError::Parse {
+            message: toml_error.to_string(),
+        })
+    }
+
+    /************ Builders ************/
+
+    /// Create the scene objects, resolving each object's material and each material's
+    /// texture by name
+    pub fn build(&self) -> Result<TraceableGroup, SceneError> {
+        let mut textures = HashMap::new();
+        for (name, description) in &self.textures {
+            let texture = description.build(&self.directory).map_err(|reason| {
+                SceneError::InvalidTexture {
+                    texture: name.clone(),
+                    reason,
+                }
+            })?;
+            textures.insert(name.clone(), texture);
+        }
+
+        let mut materials = HashMap::new();
+        for (name, description) in &self.materials {
+            let material =
+                description
+                    .build(&textures)
+                    .map_err(|texture| SceneError::UnknownTexture {
+                        material: name.clone(),
+                        texture,
+                    })?;
+            materials.insert(name.clone(), material);
+        }
+
+        let mut scene_objects = TraceableGroup { objects: vec![] };
+
+        for (index, object) in self.objects.iter().enumerate() {
+            let object_label = match object.name() {
+                Some(name) => format!("Object #{} ('{}')", index, name),
+                None => format!("Object #{}", index),
+            };
+
+            let material = match object.material() {
+                Some(material_name) => Some(
+                    materials
+                        .get(material_name)
+                        .ok_or_else(|| SceneError::UnknownMaterial {
+                            object: object_label.clone(),
+                            material: material_name.clone(),
+                        })?
+                        .clone_box(),
+                ),
+                None => None,
+            };
+
+            let traceable: Result<Box<dyn Traceable>, String> = match (object, material) {
+                (ObjectDescription::Sphere { center, radius, .. }, Some(material)) => {
+                    Sphere::new(center, *radius, material)
+                        .map(|sphere| Box::new(sphere) as Box<dyn Traceable>)
+                        .map_err(|reason| reason.to_string())
+                }
+                (
+                    ObjectDescription::Triangle {
+                        vertices,
+                        normals,
+                        texture_coords,
+                        ..
+                    },
+                    Some(material),
+                ) => match normals {
+                    Some(normals) => Triangle::with_normals(*vertices, *normals, material),
+                    None => Triangle::new(*vertices, material),
+                }
+                .map(|mut triangle| {
+                    if let Some(texture_coords) = texture_coords {
+                        triangle.set_texture_coords(*texture_coords);
+                    }
+                    Box::new(triangle) as Box<dyn Traceable>
+                })
+                .map_err(|reason| reason.to_string()),
+                (
+                    ObjectDescription::Mesh {
+                        vertices,
+                        normals,
+                        texture_coords,
+                        indices,
+                        ..
+                    },
+                    Some(material),
+                ) => TriangleMesh::with_texture_coords(
+                    vertices.clone(),
+                    normals.clone(),
+                    texture_coords.clone(),
+                    indices.clone(),
+                    material,
+                )
+                .map(|mesh| Box::new(mesh) as Box<dyn Traceable>)
+                .map_err(|reason| reason.to_string()),
+                (ObjectDescription::Model { file, .. }, material) => {
+                    let obj_file = self.directory.join(file);
+                    match SceneDescription::build_model(&obj_file.to_string_lossy(), material) {
+                        // A model without faces adds nothing to the scene
+                        Ok(meshes) if meshes.objects.is_empty() => continue,
+                        Ok(meshes) => Ok(Box::new(meshes) as Box<dyn Traceable>),
+                        Err(obj_error) => Err(obj_error.to_string()),
+                    }
+                }
+                (_, None) => Err("Object has no material".to_string()),
+            };
+
+            scene_objects.add(traceable.map_err(|reason| SceneError::InvalidObject {
+                object: object_label.clone(),
+                reason,
+            })?);
+        }
+
+        Ok(scene_objects)
+    }
+
+    /// Load an OBJ model, using its MTL materials unless a material is given
+    fn build_model(
+        obj_file: &str,
+        material: Option<Box<dyn Material>>,
+    ) -> Result<TraceableGroup, ObjError> {
+        let model = ObjModel::from_file(obj_file)?;
+        let materials = match material {
+            Some(_) => HashMap::new(),
+            None => model.load_materials()?,
+        };
+
+        model.build(material.as_deref(), &materials)
+    }
+}
@@ -1,46 +1,90 @@
 #![allow(dead_code)]
 
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
 use crate::types::vec3::Vec3;
 
 // Constants
-pub const MAX_F64: f64 = std::f64::MAX;
+pub const MAX_F64: f64 = f64::MAX;
 pub const PI: f64 = std::f64::consts::PI;
 
+/// Generator behind all random_* helpers. Every render thread owns its generators,
+/// so the same seeds always give the same samples. ChaCha8 keeps its sequences across
+/// rand versions, unlike StdRng
+pub type RandomGenerator = ChaCha8Rng;
+
 // Utility Functions
 
 pub fn degrees_to_radians(degrees: f64) -> f64 {
-    return degrees * PI / 180.0;
+    degrees * PI / 180.0
 }
 
-pub fn random_f64() -> f64 {
-    rand::random::<f64>()
+pub fn seeded_generator(seed: u64) -> RandomGenerator {
+    ChaCha8Rng::seed_from_u64(seed)
 }
 
-pub fn random_f64_between(min_inclusive: f64, max_exclusive: f64) -> f64 {
-    min_inclusive + (max_exclusive - min_inclusive) * random_f64()
+/// Mix a value into a seed (SplitMix64 finalizer), giving well spread seeds even for
+/// consecutive values
+pub fn mix_seed(seed: u64, value: u64) -> u64 {
+    let mut z = seed ^ value.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
-pub fn random_point_in_unit_sphere() -> Vec3 {
+/// Seed for renders that do not configure one
+pub fn entropy_seed() -> u64 {
+    ChaCha8Rng::from_entropy().gen::<u64>()
+}
+
+pub fn random_f64(rng: &mut RandomGenerator) -> f64 {
+    rng.gen::<f64>()
+}
+
+pub fn random_u64(rng: &mut RandomGenerator) -> u64 {
+    rng.gen::<u64>()
+}
+
+pub fn random_f64_between(
+    rng: &mut RandomGenerator,
+    min_inclusive: f64,
+    max_exclusive: f64,
+) -> f64 {
+    min_inclusive + (max_exclusive - min_inclusive) * random_f64(rng)
+}
+
+pub fn random_point_in_unit_sphere(rng: &mut RandomGenerator) -> Vec3 {
     loop {
-        let candidate = Vec3::random_between(-1.0, 1.0);
+        let candidate = Vec3::random_between(rng, -1.0, 1.0);
         if candidate.len_squared() < 1.0 {
             return candidate;
         }
     }
 }
 
-pub fn random_point_in_hemisphere(normal: &Vec3) -> Vec3 {
-    let point_in_unit_sphere = random_point_in_unit_sphere();
+pub fn random_point_in_hemisphere(rng: &mut RandomGenerator, normal: &Vec3) -> Vec3 {
+    let point_in_unit_sphere = random_point_in_unit_sphere(rng);
     if point_in_unit_sphere.dot(normal) > 0.0 {
         // In the same hemisphere as the normal
-        return point_in_unit_sphere;
+        point_in_unit_sphere
     } else {
-        return -point_in_unit_sphere;
+        -point_in_unit_sphere
+    }
+}
+
+pub fn random_point_in_unit_disk(rng: &mut RandomGenerator) -> Vec3 {
+    loop {
+        let candidate =
+            Vec3::new(random_f64_between(rng, -1.0, 1.0), random_f64_between(rng, -1.0, 1.0), 0.0);
+        if candidate.len_squared() < 1.0 {
+            return candidate;
+        }
     }
 }
 
-pub fn random_unit_vector() -> Vec3 {
-    random_point_in_unit_sphere().unit_vector().unwrap()
+pub fn random_unit_vector(rng: &mut RandomGenerator) -> Vec3 {
+    random_point_in_unit_sphere(rng).unit_vector().unwrap()
 }
 
 pub fn fmin(lhs: f64, rhs: f64) -> f64 {